@@ -0,0 +1,76 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use exhaustive_map::{BitArray, ExhaustiveMap, Finite};
+
+fn bench_bool_array_encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bool_array_encoding");
+    group.bench_function("[bool; 16]", |b| {
+        b.iter(|| {
+            (0..<[bool; 16]>::INHABITANTS)
+                .map(|i| <[bool; 16]>::from_usize(black_box(i)).unwrap().to_usize())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("BitArray<16>", |b| {
+        b.iter(|| {
+            (0..BitArray::<16>::INHABITANTS)
+                .map(|i| BitArray::<16>::from_usize(black_box(i)).unwrap().to_usize())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+fn bench_entries_indexed(c: &mut Criterion) {
+    let map = ExhaustiveMap::<[bool; 16], u32>::from_fn(|k| k.to_usize() as u32);
+    let mut group = c.benchmark_group("entries_indexed");
+    group.bench_function("iter", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map(|(k, v)| k[0] as u32 + v)
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("entries_indexed", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .entries_indexed()
+                .map(|(i, v)| (i & 1) as u32 + v)
+                .sum::<u32>()
+        })
+    });
+    group.finish();
+}
+
+fn bench_with_keys(c: &mut Criterion) {
+    let map = ExhaustiveMap::<[bool; 16], u32>::from_fn(|k| k.to_usize() as u32);
+    let keyed = map.clone().with_keys();
+    let mut group = c.benchmark_group("with_keys");
+    group.bench_function("iter", |b| {
+        b.iter(|| {
+            black_box(&map)
+                .iter()
+                .map(|(k, v)| k[0] as u32 + v)
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("KeyedMap::iter", |b| {
+        b.iter(|| {
+            black_box(&keyed)
+                .iter()
+                .map(|(k, v)| k[0] as u32 + v)
+                .sum::<u32>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_bool_array_encoding,
+    bench_entries_indexed,
+    bench_with_keys
+);
+criterion_main!(benches);
@@ -1,9 +1,11 @@
+use std::collections::HashSet;
+
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Field, Fields,
-    GenericParam, Generics, Ident, Index, LitInt, Path, Variant,
+    parse_macro_input, parse_quote, spanned::Spanned, visit::Visit, Attribute, Data, DeriveInput,
+    Field, Fields, GenericParam, Generics, Ident, Index, LitInt, Path, TypePath, Variant,
 };
 
 #[proc_macro]
@@ -21,12 +23,13 @@ pub fn __impl_tuples(input: TokenStream) -> TokenStream {
 
         let rev_indices = indices.clone().map(Index::from).rev();
         let rev_idents = idents.iter().rev();
+        let inhabitants = checked_product(idents.iter().map(|ident| quote!(#ident::INHABITANTS)));
 
         res.push(
             quote! {
                 #[automatically_derived]
                 impl <#( #idents: ::exhaustive_map::Finite ),*> ::exhaustive_map::Finite for (#( #idents, )*) {
-                    const INHABITANTS: usize = 1 #( * #idents::INHABITANTS )*;
+                    const INHABITANTS: usize = #inhabitants;
 
                     fn to_usize(&self) -> usize {
                         let mut res = 0;
@@ -57,53 +60,206 @@ pub fn __impl_tuples(input: TokenStream) -> TokenStream {
     res.into_iter().collect()
 }
 
-#[proc_macro_derive(Finite, attributes(__finite_foreign))]
+#[proc_macro_derive(Finite, attributes(finite))]
 pub fn finite_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let foreign_attrs: Vec<_> = input
-        .attrs
-        .iter()
-        .filter(|attr| attr.path().is_ident("__finite_foreign"))
-        .collect();
+    let options = match Options::parse(&input.attrs) {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    if let Data::Union(data) = &input.data {
+        return syn::Error::new(
+            data.union_token.span(),
+            "Finite cannot be derived for unions",
+        )
+        .to_compile_error()
+        .into();
+    }
 
-    let path = match foreign_attrs[..] {
-        [] => input.ident.into(),
-        [attr] => match attr.parse_args() {
-            Ok(path) => path,
+    if let Err(e) = reject_non_exhaustive(&input).and_then(|()| reject_recursive(&input)) {
+        return e.to_compile_error().into();
+    }
+
+    let (path, is_foreign) = match &options.foreign {
+        Some(path) => (path.clone(), true),
+        None => (input.ident.into(), false),
+    };
+
+    let mut res = impl_finite(&path, input.generics.clone(), &input.data, &options);
+    if let Some(span) = options.variant_index {
+        match impl_variant_index(&path, &input.generics, &input.data, is_foreign, span) {
+            Ok(imp) => res.extend(imp),
             Err(e) => return e.to_compile_error().into(),
-        },
-        _ => panic!("Only one `finite_foreign` attribute allowed"),
+        }
+    }
+    if let Some(span) = options.const_fns {
+        match impl_const_finite(&path, input.generics, &input.data, is_foreign, span) {
+            Ok(imp) => res.extend(imp),
+            Err(e) => return e.to_compile_error().into(),
+        }
+    }
+    res.into()
+}
+
+/// Returns an error if the type or one of its variants is `#[non_exhaustive]`,
+/// as adding fields or variants would change the encoding.
+fn reject_non_exhaustive(input: &DeriveInput) -> syn::Result<()> {
+    let mut attrs: Vec<&Attribute> = input.attrs.iter().collect();
+    if let Data::Enum(data) = &input.data {
+        attrs.extend(data.variants.iter().flat_map(|v| &v.attrs));
+    }
+    match attrs
+        .into_iter()
+        .find(|attr| attr.path().is_ident("non_exhaustive"))
+    {
+        Some(attr) => Err(syn::Error::new(
+            attr.span(),
+            "`Finite` can't be derived for `#[non_exhaustive]` types",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Returns an error if a field type refers to the type itself outside of `PhantomData`,
+/// such as in `Box<Self>`, as such a type would have infinitely many inhabitants.
+///
+/// Indirect recursion through other types isn't detected here.
+fn reject_recursive(input: &DeriveInput) -> syn::Result<()> {
+    struct Visitor<'a> {
+        ident: &'a Ident,
+        found: Option<Span>,
+    }
+
+    impl<'ast> Visit<'ast> for Visitor<'_> {
+        fn visit_type_path(&mut self, ty: &'ast TypePath) {
+            if ty.qself.is_none() {
+                if let Some(last) = ty.path.segments.last() {
+                    if last.ident == "PhantomData" {
+                        return;
+                    }
+                }
+                if let Some(ident) = ty.path.get_ident() {
+                    if ident == self.ident || ident == "Self" {
+                        self.found.get_or_insert(ty.span());
+                    }
+                }
+            }
+            syn::visit::visit_type_path(self, ty);
+        }
+    }
+
+    let mut visitor = Visitor {
+        ident: &input.ident,
+        found: None,
     };
+    for field in fields(&input.data) {
+        visitor.visit_type(&field.ty);
+    }
+    match visitor.found {
+        Some(span) => Err(syn::Error::new(
+            span,
+            "`Finite` can't be derived for recursive types, as they have infinitely many inhabitants",
+        )),
+        None => Ok(()),
+    }
+}
 
-    impl_finite(&path, input.generics, &input.data).into()
+/// The options given in `#[finite(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    max_inhabitants: Option<LitInt>,
+    foreign: Option<Path>,
+    /// The span of the `variant_index` option, if given.
+    variant_index: Option<Span>,
+    /// The span of the `const_fns` option, if given.
+    const_fns: Option<Span>,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("finite")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("max_inhabitants") {
+                    options.max_inhabitants = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("foreign") {
+                    if options.foreign.is_some() {
+                        return Err(meta.error("duplicate `foreign` attribute"));
+                    }
+                    options.foreign = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("variant_index") {
+                    options.variant_index = Some(meta.path.span());
+                    Ok(())
+                } else if meta.path.is_ident("const_fns") {
+                    options.const_fns = Some(meta.path.span());
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `finite` attribute"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
 }
 
-fn impl_finite(path: &Path, generics: Generics, data: &Data) -> proc_macro2::TokenStream {
-    let generics = add_trait_bounds(generics);
+fn impl_finite(
+    path: &Path,
+    generics: Generics,
+    data: &Data,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let generics = add_trait_bounds(generics, data);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let FiniteImpl {
-        inhabitants,
+        mut inhabitants,
         to_usize,
         from_usize,
     } = finite_impl(data);
 
+    let mut assertion = quote!();
+    if let Some(max) = &options.max_inhabitants {
+        let message = "the number of inhabitants exceeds `max_inhabitants`";
+        if generics.params.is_empty() {
+            assertion = quote! {
+                const _: () = assert!(
+                    <#path as ::exhaustive_map::Finite>::INHABITANTS <= #max,
+                    #message
+                );
+            };
+        } else {
+            // Generic types are checked when `INHABITANTS` is used for a specific instantiation.
+            inhabitants = quote! {
+                {
+                    let n = #inhabitants;
+                    assert!(n <= #max, #message);
+                    n
+                }
+            };
+        }
+    }
+
     quote! {
+        #assertion
+
         #[automatically_derived]
         impl #impl_generics ::exhaustive_map::Finite for #path #ty_generics #where_clause {
             const INHABITANTS: usize = #inhabitants;
 
             #[allow(non_snake_case)]
             fn to_usize(&self) -> usize {
-                let v = self;
+                let __v = self;
                 #to_usize
             }
 
             #[allow(clippy::let_unit_value)]
             #[allow(clippy::modulo_one)]
-            fn from_usize(mut i: usize) -> Option<Self> {
-                if i >= Self::INHABITANTS {
+            fn from_usize(mut __i: usize) -> Option<Self> {
+                if __i >= Self::INHABITANTS {
                     return None;
                 }
                 #from_usize
@@ -112,17 +268,170 @@ fn impl_finite(path: &Path, generics: Generics, data: &Data) -> proc_macro2::Tok
     }
 }
 
-fn add_trait_bounds(mut generics: Generics) -> Generics {
+/// For fieldless enums with the `const_fns` option, implements `ConstFinite` and adds inherent
+/// `const fn` versions of `to_usize` and `from_usize`.
+fn impl_const_finite(
+    path: &Path,
+    generics: Generics,
+    data: &Data,
+    is_foreign: bool,
+    span: Span,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let enum_data = match data {
+        Data::Enum(enum_data)
+            if enum_data
+                .variants
+                .iter()
+                .all(|v| matches!(v.fields, Fields::Unit)) =>
+        {
+            enum_data
+        }
+        _ => {
+            return Err(syn::Error::new(
+                span,
+                "`const_fns` is only supported for enums without fields",
+            ))
+        }
+    };
+    if is_foreign {
+        return Err(syn::Error::new(
+            span,
+            "`const_fns` can't be used together with `foreign`",
+        ));
+    }
+
+    let generics = add_trait_bounds(generics, data);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let names: Vec<_> = enum_data.variants.iter().map(|v| &v.ident).collect();
+    let indices: Vec<_> = (0..names.len()).collect();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::exhaustive_map::ConstFinite for #path #ty_generics #where_clause {}
+
+        #[automatically_derived]
+        impl #impl_generics #path #ty_generics #where_clause {
+            /// The same as [`Finite::to_usize`](::exhaustive_map::Finite::to_usize),
+            /// but usable in const contexts.
+            #[allow(dead_code)]
+            pub const fn to_usize_const(&self) -> usize {
+                match *self {
+                    #(Self::#names => #indices,)*
+                }
+            }
+
+            /// The same as [`Finite::from_usize`](::exhaustive_map::Finite::from_usize),
+            /// but usable in const contexts.
+            #[allow(dead_code)]
+            pub const fn from_usize_const(__i: usize) -> Option<Self> {
+                match __i {
+                    #(#indices => Some(Self::#names),)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+/// For enums with the `variant_index` option, adds an inherent `variant_index` method
+/// returning the index of the variant, ignoring any fields.
+fn impl_variant_index(
+    path: &Path,
+    generics: &Generics,
+    data: &Data,
+    is_foreign: bool,
+    span: Span,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(enum_data) = data else {
+        return Err(syn::Error::new(
+            span,
+            "`variant_index` is only supported for enums",
+        ));
+    };
+    if is_foreign {
+        return Err(syn::Error::new(
+            span,
+            "`variant_index` can't be used together with `foreign`",
+        ));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let names = enum_data.variants.iter().map(|v| &v.ident);
+    let indices = 0..enum_data.variants.len();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #path #ty_generics #where_clause {
+            /// Returns the index of the variant in declaration order, ignoring any fields.
+            #[allow(dead_code)]
+            pub const fn variant_index(&self) -> usize {
+                match *self {
+                    #(Self::#names { .. } => #indices,)*
+                }
+            }
+        }
+    })
+}
+
+fn add_trait_bounds(mut generics: Generics, data: &Data) -> Generics {
+    let used = used_type_params(&generics, data);
     for param in &mut generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
-            type_param
-                .bounds
-                .push(parse_quote!(::exhaustive_map::Finite));
+            if used.contains(&type_param.ident) {
+                type_param
+                    .bounds
+                    .push(parse_quote!(::exhaustive_map::Finite));
+            }
         }
     }
     generics
 }
 
+/// Returns the type parameters used in the field types outside of `PhantomData`,
+/// as `PhantomData<T>` implements `Finite` for any `T`.
+fn used_type_params(generics: &Generics, data: &Data) -> HashSet<Ident> {
+    struct Visitor {
+        params: HashSet<Ident>,
+        used: HashSet<Ident>,
+    }
+
+    impl<'ast> Visit<'ast> for Visitor {
+        fn visit_type_path(&mut self, ty: &'ast TypePath) {
+            if ty.qself.is_none() {
+                if let Some(last) = ty.path.segments.last() {
+                    if last.ident == "PhantomData" {
+                        return;
+                    }
+                }
+                if let Some(ident) = ty.path.get_ident() {
+                    if self.params.contains(ident) {
+                        self.used.insert(ident.clone());
+                    }
+                }
+            }
+            syn::visit::visit_type_path(self, ty);
+        }
+    }
+
+    let mut visitor = Visitor {
+        params: generics.type_params().map(|p| p.ident.clone()).collect(),
+        used: HashSet::new(),
+    };
+    for field in fields(data) {
+        visitor.visit_type(&field.ty);
+    }
+    visitor.used
+}
+
+/// Returns the fields of a struct or union, or of all variants of an enum.
+fn fields(data: &Data) -> Vec<&Field> {
+    match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    }
+}
+
 struct FiniteImpl {
     inhabitants: proc_macro2::TokenStream,
     to_usize: proc_macro2::TokenStream,
@@ -173,7 +482,7 @@ fn finite_impl(data: &Data) -> FiniteImpl {
 
                     quote! {
                         {
-                            let Self { #(#names: #mapped_names,)* } = v;
+                            let Self { #(#names: #mapped_names,)* } = __v;
                             #to_usize
                         }
                     }
@@ -205,24 +514,24 @@ fn finite_impl(data: &Data) -> FiniteImpl {
                 .collect();
 
             FiniteImpl {
-                inhabitants: quote!(0 #(+ #inhabitants)*),
+                inhabitants: checked_sum(inhabitants.iter().cloned()),
                 to_usize: quote! {
-                    match *v {
+                    match *__v {
                         #(#to_usize,)*
                     }
                 },
                 from_usize: quote! {
                     #(
-                        if i < #inhabitants {
+                        if __i < #inhabitants {
                             return #from_usize;
                         }
-                        i -= #inhabitants;
+                        __i -= #inhabitants;
                     )*
                     unreachable!()
                 },
             }
         }
-        Data::Union(_) => panic!("Finite can't be derived for unions"),
+        Data::Union(_) => unreachable!("unions are rejected in `finite_derive`"),
     }
 }
 
@@ -251,11 +560,11 @@ fn finite_impl_for_variant(variant: &Variant, offset: proc_macro2::TokenStream)
         }
         Fields::Unnamed(_) => {
             let field_names: Vec<_> = (0..variant.fields.len())
-                .map(|i| Ident::new(&format!("v_{i}"), Span::call_site()))
+                .map(|i| Ident::new(&format!("__v_{i}"), Span::call_site()))
                 .collect();
             quote! {
                 Self::#name(#(ref #field_names,)*) => {
-                    let v = (#(#field_names,)*);
+                    let __v = (#(#field_names,)*);
                     (#to_usize + #offset)
                 }
             }
@@ -300,15 +609,15 @@ fn finite_impl_for_fields(fields: &Fields, constructor: proc_macro2::TokenStream
     };
 
     FiniteImpl {
-        inhabitants: quote!(1 #(* #inhabitants)*),
+        inhabitants: checked_product(inhabitants.iter().cloned()),
         to_usize: quote! {
             {
-                let mut res = 0;
+                let mut __res = 0;
                 #(
-                    res *= #inhabitants;
-                    res += #to_usize;
+                    __res *= #inhabitants;
+                    __res += #to_usize;
                 )*
-                res
+                __res
             }
         },
         from_usize,
@@ -328,7 +637,7 @@ fn finite_impl_for_field(field: &Field, i: usize) -> FiniteImpl {
         Some(name) => mapped_field_name(name),
         None => {
             let index = Index::from(i);
-            quote!(&v.#index)
+            quote!(&__v.#index)
         }
     };
     let inhabitants = quote_spanned! { field.span() =>
@@ -340,16 +649,32 @@ fn finite_impl_for_field(field: &Field, i: usize) -> FiniteImpl {
         },
         from_usize: quote_spanned! { field.span() =>
             {
-                let v = <#ty as ::exhaustive_map::Finite>::from_usize(i % #inhabitants).unwrap();
-                i /= #inhabitants;
-                v
+                let __v = <#ty as ::exhaustive_map::Finite>::from_usize(__i % #inhabitants).unwrap();
+                __i /= #inhabitants;
+                __v
             }
         },
         inhabitants,
     }
 }
 
+fn checked_sum(terms: impl Iterator<Item = proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+    terms.fold(
+        quote!(0),
+        |acc, term| quote!(::exhaustive_map::__inhabitants_add(#acc, #term)),
+    )
+}
+
+fn checked_product(
+    factors: impl Iterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    factors.fold(
+        quote!(1),
+        |acc, factor| quote!(::exhaustive_map::__inhabitants_mul(#acc, #factor)),
+    )
+}
+
 fn mapped_field_name(ident: &Ident) -> proc_macro2::TokenStream {
-    let ident = format_ident!("field_{}", ident);
+    let ident = format_ident!("__field_{}", ident);
     quote!(#ident)
 }
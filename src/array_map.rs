@@ -0,0 +1,203 @@
+use std::{
+    borrow::Borrow,
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    finite::{nth_inhabitant, Finite, FiniteExt},
+    map::{Iter, IterMut, Values, ValuesMut},
+};
+
+/// A map which is guaranteed to always contain a value for each possible key of type `K`,
+/// storing the values inline in an array of length `N` instead of on the heap.
+///
+/// `N` must be equal to `K::INHABITANTS`, which is checked at compile time.
+/// ```
+/// use exhaustive_map::ExhaustiveArrayMap;
+///
+/// let mut map = ExhaustiveArrayMap::<bool, u8, 2>::from_fn(|k| k as u8);
+/// assert_eq!(map[false], 0);
+/// assert_eq!(map[true], 1);
+///
+/// map[true] = 5;
+/// assert_eq!(map.into_array(), [0, 5]);
+/// ```
+///
+/// Using the wrong length fails to compile:
+/// ```compile_fail
+/// use exhaustive_map::ExhaustiveArrayMap;
+///
+/// let map = ExhaustiveArrayMap::<bool, u8, 3>::from_fn(|k| k as u8);
+/// ```
+/// ```compile_fail
+/// use exhaustive_map::ExhaustiveArrayMap;
+///
+/// let map = ExhaustiveArrayMap::<bool, u8, 3>::new([0; 3]);
+/// ```
+#[repr(transparent)]
+pub struct ExhaustiveArrayMap<K: Finite, V, const N: usize> {
+    array: [V; N],
+    _phantom: PhantomData<fn() -> K>,
+}
+
+impl<K: Finite, V, const N: usize> ExhaustiveArrayMap<K, V, N> {
+    const ASSERT_LEN: () = assert!(
+        N == K::INHABITANTS,
+        "the length of the array must be equal to the number of inhabitants of the key"
+    );
+
+    /// Creates a map from an array of values, ordered by the keys order provided by [`Finite`].
+    pub const fn new(array: [V; N]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ASSERT_LEN;
+        Self {
+            array,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a map by providing a mapping function from `K` to `V`.
+    ///
+    /// Similar to [`array::from_fn`](std::array::from_fn).
+    pub fn from_fn(mut f: impl FnMut(K) -> V) -> Self {
+        Self::new(std::array::from_fn(|i| f(nth_inhabitant(i))))
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// Always equal to `K::INHABITANTS`.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// An iterator visiting all values stored in the map, ordered by the keys order provided by [`Finite`].
+    pub fn values(&self) -> Values<'_, V> {
+        Values(self.array.iter())
+    }
+
+    /// A mutable iterator visiting all values stored in the map, ordered by the keys order provided by [`Finite`].
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        ValuesMut(self.array.iter_mut())
+    }
+
+    /// An iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(K::iter_all().zip(self.values()))
+    }
+
+    /// A mutable iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut(K::iter_all().zip(self.values_mut()))
+    }
+
+    /// Returns the values as an array, ordered by the keys order provided by [`Finite`].
+    pub fn into_array(self) -> [V; N] {
+        self.array
+    }
+}
+
+impl<K: Finite, V: Default, const N: usize> Default for ExhaustiveArrayMap<K, V, N> {
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<'a, K: Finite, V, const N: usize> IntoIterator for &'a ExhaustiveArrayMap<K, V, N> {
+    type Item = (K, &'a V);
+
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Finite, V, const N: usize> IntoIterator for &'a mut ExhaustiveArrayMap<K, V, N> {
+    type Item = (K, &'a mut V);
+
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Finite + Debug, V: Debug, const N: usize> Debug for ExhaustiveArrayMap<K, V, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: Finite, V, const N: usize, Q: Borrow<K>> Index<Q> for ExhaustiveArrayMap<K, V, N> {
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        &self.array[K::to_usize(index.borrow())]
+    }
+}
+
+impl<K: Finite, V, const N: usize, Q: Borrow<K>> IndexMut<Q> for ExhaustiveArrayMap<K, V, N> {
+    fn index_mut(&mut self, index: Q) -> &mut Self::Output {
+        &mut self.array[K::to_usize(index.borrow())]
+    }
+}
+
+// The following traits could have been implemented using a derive macro,
+// however that would put an unnecessary trait bound on the key.
+
+impl<K: Finite, V: Clone, const N: usize> Clone for ExhaustiveArrayMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            array: self.array.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K: Finite, V: PartialEq, const N: usize> PartialEq for ExhaustiveArrayMap<K, V, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.array.eq(&other.array)
+    }
+}
+
+impl<K: Finite, V: Eq, const N: usize> Eq for ExhaustiveArrayMap<K, V, N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Finite)]
+    struct Key(PhantomData<*mut u8>);
+
+    #[allow(unused)]
+    const fn assert_implements_traits<T: Send + Sync + Default + Clone + PartialEq + Eq>() {}
+
+    const _: () = assert_implements_traits::<ExhaustiveArrayMap<Key, bool, 1>>();
+
+    #[test]
+    fn test_bool_keys() {
+        let mut m = ExhaustiveArrayMap::<bool, u8, 2>::default();
+        m[true] = 3;
+        m[&false] = 2;
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.iter().collect::<Vec<_>>(), [(false, &2), (true, &3)]);
+
+        for (k, v) in &mut m {
+            *v += k as u8;
+        }
+        assert_eq!(m.values().collect::<Vec<_>>(), [&2, &4]);
+        assert_eq!(m, ExhaustiveArrayMap::new([2, 4]));
+        assert_eq!(format!("{m:?}"), "{false: 2, true: 4}");
+    }
+}
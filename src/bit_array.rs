@@ -0,0 +1,85 @@
+use crate::Finite;
+
+/// An array of `N` booleans encoded as an `N`-bit number.
+///
+/// This uses the same encoding as the [`Finite`] implementation for `[bool; N]`,
+/// but encoding and decoding is done using bit shifts instead of divisions.
+///
+/// ```
+/// use exhaustive_map::{BitArray, Finite};
+///
+/// let bits = BitArray([true, false, true]);
+/// assert_eq!(bits.to_usize(), 0b101);
+/// assert_eq!(bits.to_usize(), bits.0.to_usize());
+/// assert_eq!(
+///     BitArray::<3>::from_usize(0b110),
+///     Some(BitArray([false, true, true]))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitArray<const N: usize>(pub [bool; N]);
+
+impl<const N: usize> From<[bool; N]> for BitArray<N> {
+    fn from(value: [bool; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> From<BitArray<N>> for [bool; N] {
+    fn from(value: BitArray<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> Finite for BitArray<N> {
+    const INHABITANTS: usize = {
+        if N >= usize::BITS as usize {
+            panic!("the number of inhabitants exceeds usize::MAX");
+        }
+        1 << N
+    };
+
+    fn to_usize(&self) -> usize {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(0, |res, (k, &b)| res | (b as usize) << k)
+    }
+
+    fn from_usize(i: usize) -> Option<Self> {
+        if i >= Self::INHABITANTS {
+            return None;
+        }
+        Some(Self(std::array::from_fn(|k| (i >> k) & 1 == 1)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FiniteExt;
+
+    fn test_same_encoding<const N: usize>() {
+        assert_eq!(BitArray::<N>::INHABITANTS, <[bool; N]>::INHABITANTS);
+        for (bits, arr) in BitArray::<N>::iter_all().zip(<[bool; N]>::iter_all()) {
+            assert_eq!(bits.0, arr);
+            assert_eq!(bits.to_usize(), arr.to_usize());
+        }
+        assert_eq!(BitArray::<N>::from_usize(BitArray::<N>::INHABITANTS), None);
+    }
+
+    #[test]
+    fn test_bit_array_0() {
+        test_same_encoding::<0>();
+    }
+
+    #[test]
+    fn test_bit_array_1() {
+        test_same_encoding::<1>();
+    }
+
+    #[test]
+    fn test_bit_array_10() {
+        test_same_encoding::<10>();
+    }
+}
@@ -1,12 +1,15 @@
 use std::{
     borrow::Cow,
+    iter::StepBy,
+    marker::PhantomData,
     num::{NonZeroI16, NonZeroI32, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU8},
+    ops::Range,
     rc::Rc,
     sync::Arc,
 };
 
-pub use exhaustive_map_macros::Finite;
 use exhaustive_map_macros::__impl_tuples;
+pub use exhaustive_map_macros::Finite;
 
 /// Represents a type that has a finite number of inhabitants.
 ///
@@ -14,6 +17,11 @@ use exhaustive_map_macros::__impl_tuples;
 /// such as `usize`, `isize`, `u64`, `i64` and `f64`,
 /// then `Finite` should not be implemented for the type.
 ///
+/// Types with `2^32` inhabitants, such as `u32`, `i32`, `f32` and [`Ipv4Addr`](std::net::Ipv4Addr),
+/// have one more inhabitant than `usize::MAX` on 32-bit targets,
+/// so `Finite` is only implemented for them on 64-bit targets.
+/// [`NonZeroU32`] and [`NonZeroI32`] have `2^32 - 1` inhabitants, so they are supported on all targets.
+///
 /// Example:
 /// ```
 /// use exhaustive_map::{Finite, FiniteExt};
@@ -34,8 +42,224 @@ use exhaustive_map_macros::__impl_tuples;
 /// let all: Vec<_> = Color::iter_all().collect();
 /// assert_eq!(all, vec![Color::Red, Color::Green, Color::Blue]);
 /// ```
+///
+/// # Ordering
+///
+/// The order of the inhabitants given by the provided implementations is stable.
+///
+/// For enums, such as [`Option`] and derived implementations, the inhabitants of each variant
+/// occupy a contiguous block of indices, and the blocks are ordered by the declaration order
+/// of the variants. For `Option<T>` this means that `None` has index `0` and `Some(v)` has
+/// index `1 + v.to_usize()`, which also applies when nesting:
+/// ```
+/// use exhaustive_map::Finite;
+///
+/// assert_eq!(None::<Option<bool>>.to_usize(), 0);
+/// assert_eq!(Some(None::<bool>).to_usize(), 1);
+/// assert_eq!(Some(Some(false)).to_usize(), 2);
+/// assert_eq!(Some(Some(true)).to_usize(), 3);
+/// ```
+///
+/// For types with multiple fields, such as tuples, arrays and derived implementations for structs
+/// and enum variants, the fields are encoded in mixed radix in declaration order,
+/// where the first field is the least significant:
+/// ```
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// enum Key {
+///     A(u8, bool),
+/// }
+///
+/// assert_eq!(Key::A(3, false).to_usize(), 3);
+/// assert_eq!(Key::A(3, true).to_usize(), 3 + 256);
+/// assert_eq!((3u8, true).to_usize(), 3 + 256);
+/// ```
+///
+/// # References
+///
+/// `Finite` can't be implemented for references, as [`from_usize`](Finite::from_usize) would
+/// have to create a reference to a value without anywhere to store it. Instead, store all the
+/// inhabitants and refer to them by index, for example using [`IterAll::refs`]:
+/// ```
+/// use exhaustive_map::{Finite, FiniteExt};
+///
+/// #[derive(Finite, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// let all: Vec<Color> = Color::iter_all().collect();
+/// let refs: Vec<&Color> = Color::iter_all_from(1).refs(&all).collect();
+/// assert_eq!(refs, [&Color::Green, &Color::Blue]);
+/// assert_eq!(&all[Color::Blue.to_usize()], &Color::Blue);
+/// ```
+///
+/// # Limiting the number of inhabitants
+///
+/// The derive macro accepts a `#[finite(max_inhabitants = N)]` attribute,
+/// which asserts that the type has at most `N` inhabitants.
+/// This is checked at compile time, for generic types when `INHABITANTS` is used,
+/// guarding against accidentally making a key type much larger:
+/// ```
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// #[finite(max_inhabitants = 1024)]
+/// struct Key {
+///     a: u8,
+///     b: bool,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// #[finite(max_inhabitants = 1024)]
+/// struct Key {
+///     a: u8,
+///     b: u8,
+/// }
+/// ```
+///
+/// # Variant index
+///
+/// For enums, the derive macro accepts a `#[finite(variant_index)]` attribute, which adds an
+/// inherent `const fn variant_index(&self) -> usize` method returning the index of the variant
+/// in declaration order. Unlike [`to_usize`](Finite::to_usize) it ignores the fields:
+/// ```
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// #[finite(variant_index)]
+/// enum Key {
+///     A(bool),
+///     B,
+/// }
+///
+/// assert_eq!(Key::A(true).variant_index(), 0);
+/// assert_eq!(Key::B.variant_index(), 1);
+/// assert_eq!(Key::B.to_usize(), 2);
+/// ```
+///
+/// # Foreign types
+///
+/// `Finite` can't be derived for a type defined in another crate,
+/// but it can be implemented by deriving it for a local copy of the type's definition
+/// with a `#[finite(foreign = path::to::Type)]` attribute.
+/// The generated implementation is for the foreign type, so the copy must have the same
+/// variant and field names and all fields must be accessible:
+/// ```
+/// mod other_crate {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Direction {
+///         Left,
+///         Right,
+///         Steps { count: u8 },
+///     }
+/// }
+///
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// #[finite(foreign = other_crate::Direction)]
+/// enum _Direction {
+///     Left,
+///     Right,
+///     Steps { count: u8 },
+/// }
+///
+/// use other_crate::Direction;
+///
+/// assert_eq!(Direction::INHABITANTS, 258);
+/// assert_eq!(Direction::Steps { count: 3 }.to_usize(), 5);
+/// assert_eq!(Direction::from_usize(1), Some(Direction::Right));
+/// ```
+///
+/// # Non-exhaustive types
+///
+/// Deriving `Finite` for a `#[non_exhaustive]` type, or an enum with a `#[non_exhaustive]`
+/// variant, is rejected, as adding fields or variants later would change the
+/// number of inhabitants and the encoding:
+/// ```compile_fail
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// #[non_exhaustive]
+/// enum Key {
+///     A,
+///     B,
+/// }
+/// ```
+///
+/// # Unions
+///
+/// `Finite` can't be derived for unions, as it isn't known which field is in use:
+/// ```compile_fail
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// union Key {
+///     a: u8,
+///     b: bool,
+/// }
+/// ```
+///
+/// # Recursive types
+///
+/// A recursive type has infinitely many inhabitants, so deriving `Finite` for it is rejected
+/// if a field refers to the type itself:
+/// ```compile_fail
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// enum Tree {
+///     Leaf,
+///     Node(Box<Tree>, Box<Tree>),
+/// }
+/// ```
+///
+/// Recursion through other types, such as `struct A(Option<Box<B>>); struct B(A);`,
+/// isn't detected by the derive macro, and instead fails to compile with a
+/// "cycle detected" error when evaluating `INHABITANTS`.
+///
+/// # Uninhabited types
+///
+/// Uninhabited types, such as [`Infallible`](std::convert::Infallible) and derived enums without
+/// variants, have zero inhabitants, so an [`ExhaustiveMap`](crate::ExhaustiveMap) with such keys
+/// is always empty:
+/// ```
+/// use std::convert::Infallible;
+///
+/// use exhaustive_map::{ExhaustiveMap, Finite};
+///
+/// assert_eq!(Infallible::INHABITANTS, 0);
+/// assert!(ExhaustiveMap::<Infallible, String>::default().is_empty());
+/// ```
+///
+/// `Finite` is not implemented for the never type `!` as it is not stable yet.
+/// `Infallible` is intended to become an alias of `!` when it is stabilized,
+/// at which point the implementation for `Infallible` will cover it.
+#[cfg_attr(
+    not(target_pointer_width = "64"),
+    diagnostic::on_unimplemented(
+        note = "`Finite` is only implemented for `u32`, `i32`, `f32` and `Ipv4Addr` on 64-bit targets"
+    )
+)]
 pub trait Finite: Sized {
     /// The total number of different inhabitants of the type.
+    ///
+    /// This is a plain `usize`, so for a concrete type it can be used in const contexts,
+    /// such as the length of an array:
+    /// ```
+    /// use exhaustive_map::Finite;
+    ///
+    /// let counts = [0u32; <Option<bool>>::INHABITANTS];
+    /// assert_eq!(counts.len(), 3);
+    /// ```
     const INHABITANTS: usize;
 
     /// Should return a number in the range `0..INHABITANTS`.
@@ -47,32 +271,331 @@ pub trait Finite: Sized {
     fn from_usize(i: usize) -> Option<Self>;
 }
 
-/// An extension for [`Finite`] providing the [`iter_all`](FiniteExt::iter_all) method.
+/// A marker trait for types implementing [`Finite`] which can also be converted to and from
+/// `usize` in const contexts.
+///
+/// This is implemented by the derive macro for enums where no variants have any fields,
+/// when given the `#[finite(const_fns)]` attribute.
+/// Such enums additionally get inherent `const fn to_usize_const(&self) -> usize` and
+/// `const fn from_usize_const(i: usize) -> Option<Self>` methods,
+/// which behave like [`Finite::to_usize`] and [`Finite::from_usize`].
+///
+/// ```
+/// use exhaustive_map::{ConstFinite, Finite};
+///
+/// #[derive(Finite, Debug, PartialEq)]
+/// #[finite(const_fns)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// const GREEN: usize = Color::Green.to_usize_const();
+/// const BLUE: Option<Color> = Color::from_usize_const(2);
+/// assert_eq!(GREEN, 1);
+/// assert_eq!(BLUE, Some(Color::Blue));
+///
+/// fn assert_const_finite<T: ConstFinite>() {}
+/// assert_const_finite::<Color>();
+/// ```
+pub trait ConstFinite: Finite {}
+
+/// Asserts that the number of inhabitants of `T` fits in a `usize`.
+///
+/// Evaluating [`Finite::INHABITANTS`] for an implementation provided by this crate
+/// (including derived implementations) fails to compile if the number of inhabitants is more
+/// than `usize::MAX`. As associated constants are only evaluated when used, this can be used to
+/// force the check to happen:
+/// ```
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// struct Key {
+///     a: u8,
+///     b: bool,
+/// }
+///
+/// const _: () = exhaustive_map::assert_fits::<Key>();
+/// ```
+///
+/// A type with too many inhabitants results in a compile error:
+/// ```compile_fail
+/// use exhaustive_map::Finite;
+///
+/// #[derive(Finite)]
+/// struct Key {
+///     a: u32,
+///     b: u32,
+///     c: u32,
+/// }
+///
+/// const _: () = exhaustive_map::assert_fits::<Key>();
+/// ```
+pub const fn assert_fits<T: Finite>() {
+    let _ = T::INHABITANTS;
+}
+
+/// Returns whether a number of inhabitants `n` fits in a `usize`,
+/// i.e. whether a type with `n` inhabitants can implement [`Finite`] on the current target.
+///
+/// This can be used when implementing [`Finite`] manually to validate a count computed in a
+/// wider integer type.
+///
+/// ```
+/// use exhaustive_map::fits_in_usize;
+///
+/// const _: () = assert!(fits_in_usize(1 << 16));
+/// assert!(fits_in_usize(usize::MAX as u128));
+/// assert!(!fits_in_usize(usize::MAX as u128 + 1));
+/// ```
+pub const fn fits_in_usize(n: u128) -> bool {
+    n <= usize::MAX as u128
+}
+
+#[doc(hidden)]
+pub const fn __inhabitants_add(a: usize, b: usize) -> usize {
+    match a.checked_add(b) {
+        Some(v) => v,
+        None => panic!("the number of inhabitants exceeds usize::MAX"),
+    }
+}
+
+#[doc(hidden)]
+pub const fn __inhabitants_mul(a: usize, b: usize) -> usize {
+    match a.checked_mul(b) {
+        Some(v) => v,
+        None => panic!("the number of inhabitants exceeds usize::MAX"),
+    }
+}
+
+/// An extension for [`Finite`] providing the [`iter_all`](FiniteExt::iter_all) method
+/// and related methods.
 pub trait FiniteExt: Finite {
     /// An iterator over all inhabitants of the type, ordered by the order provided by [`Finite`].
     fn iter_all() -> IterAll<Self> {
-        IterAll((0..Self::INHABITANTS).map(|i| {
-            Self::from_usize(i).expect("unexpected None returned from Finite::from_usize in range")
-        }))
+        Self::iter_all_from(0)
+    }
+
+    /// An iterator over all inhabitants of the type with index at least `start`,
+    /// ordered by the order provided by [`Finite`].
+    ///
+    /// If `start` is at least `INHABITANTS` the iterator is empty.
+    ///
+    /// ```
+    /// use exhaustive_map::FiniteExt;
+    ///
+    /// let all: Vec<_> = u8::iter_all_from(250).collect();
+    /// assert_eq!(all, [250, 251, 252, 253, 254, 255]);
+    /// ```
+    fn iter_all_from(start: usize) -> IterAll<Self> {
+        IterAll::new(start.min(Self::INHABITANTS)..Self::INHABITANTS)
+    }
+
+    /// An iterator over all inhabitants of the type paired with their index,
+    /// ordered by the order provided by [`Finite`].
+    ///
+    /// The index of each inhabitant `v` is equal to [`v.to_usize()`](Finite::to_usize).
+    ///
+    /// ```
+    /// use exhaustive_map::FiniteExt;
+    ///
+    /// let all: Vec<_> = Option::<bool>::iter_all_indexed().collect();
+    /// assert_eq!(all, [(0, None), (1, Some(false)), (2, Some(true))]);
+    /// ```
+    fn iter_all_indexed() -> IterAllIndexed<Self> {
+        IterAllIndexed(0..Self::INHABITANTS, PhantomData)
+    }
+
+    /// An iterator over all inhabitants of the type split into chunks of `size` inhabitants,
+    /// ordered by the order provided by [`Finite`].
+    ///
+    /// Each chunk has exactly `size` inhabitants, except for the last chunk which may be smaller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// ```
+    /// use exhaustive_map::FiniteExt;
+    ///
+    /// let chunks: Vec<Vec<_>> = u8::iter_all_chunks(100).map(|c| c.collect()).collect();
+    /// assert_eq!(chunks.len(), 3);
+    /// assert_eq!(chunks[2], (200..=255).collect::<Vec<_>>());
+    /// ```
+    fn iter_all_chunks(size: usize) -> IterAllChunks<Self> {
+        IterAllChunks {
+            starts: (0..Self::INHABITANTS).step_by(size),
+            size,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an inhabitant of the type chosen uniformly at random.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is uninhabited, i.e. if `INHABITANTS` is zero.
+    #[cfg(feature = "rand")]
+    fn from_rng(rng: &mut impl rand::Rng) -> Self {
+        let i = rng.gen_range(0..Self::INHABITANTS);
+        nth_inhabitant(i)
     }
 }
 
 impl<T: Finite> FiniteExt for T {}
 
+/// Returns the inhabitant at index `i`, which must be less than `T::INHABITANTS`.
+pub(crate) fn nth_inhabitant<T: Finite>(i: usize) -> T {
+    T::from_usize(i).expect("unexpected None returned from Finite::from_usize in range")
+}
+
 /// An owned iterator over all inhabitants of a type implementing [`Finite`].
 ///
 /// This `struct` is created by the [`FiniteExt::iter_all`] method.
-pub struct IterAll<T>(std::iter::Map<std::ops::Range<usize>, fn(usize) -> T>);
+pub struct IterAll<T>(Range<usize>, PhantomData<fn() -> T>);
 
-impl<T> Iterator for IterAll<T> {
+impl<T: Finite> IterAll<T> {
+    fn new(range: Range<usize>) -> Self {
+        Self(range, PhantomData)
+    }
+
+    fn value(i: usize) -> T {
+        nth_inhabitant(i)
+    }
+
+    /// Converts the iterator into an iterator over references to the remaining inhabitants in
+    /// `all`, which must contain all inhabitants ordered by the order provided by [`Finite`],
+    /// such as the values of [`iter_all`](FiniteExt::iter_all) collected into a `Vec`.
+    ///
+    /// This doesn't call [`from_usize`](Finite::from_usize).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `all` is not `T::INHABITANTS`.
+    pub fn refs(self, all: &[T]) -> std::slice::Iter<'_, T> {
+        assert_eq!(
+            all.len(),
+            T::INHABITANTS,
+            "the slice must contain all inhabitants"
+        );
+        all[self.0].iter()
+    }
+
+    /// Returns the range of indices, as given by [`to_usize`](Finite::to_usize),
+    /// of the inhabitants not yet returned by the iterator.
+    ///
+    /// ```
+    /// use exhaustive_map::FiniteExt;
+    ///
+    /// let mut iter = u8::iter_all();
+    /// iter.next();
+    /// iter.next_back();
+    /// assert_eq!(iter.remaining(), 1..255);
+    /// ```
+    pub fn remaining(&self) -> Range<usize> {
+        self.0.clone()
+    }
+}
+
+impl<T: Finite> Iterator for IterAll<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.next().map(Self::value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    // The following methods are overridden to avoid calling `from_usize`
+    // for the inhabitants that are skipped.
+
+    fn count(self) -> usize {
+        self.0.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).map(Self::value)
+    }
+}
+
+impl<T: Finite> DoubleEndedIterator for IterAll<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(Self::value)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth_back(n).map(Self::value)
+    }
+}
+
+impl<T: Finite> ExactSizeIterator for IterAll<T> {}
+
+/// An owned iterator over all inhabitants of a type implementing [`Finite`] paired with their index.
+///
+/// This `struct` is created by the [`FiniteExt::iter_all_indexed`] method.
+pub struct IterAllIndexed<T>(Range<usize>, PhantomData<fn() -> T>);
+
+impl<T: Finite> IterAllIndexed<T> {
+    fn with_value(i: usize) -> (usize, T) {
+        (i, IterAll::value(i))
+    }
+}
+
+impl<T: Finite> Iterator for IterAllIndexed<T> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Self::with_value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Finite> DoubleEndedIterator for IterAllIndexed<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(Self::with_value)
+    }
+}
+
+impl<T: Finite> ExactSizeIterator for IterAllIndexed<T> {}
+
+/// An iterator over chunks of all inhabitants of a type implementing [`Finite`].
+///
+/// This `struct` is created by the [`FiniteExt::iter_all_chunks`] method.
+pub struct IterAllChunks<T> {
+    starts: StepBy<Range<usize>>,
+    size: usize,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Finite> Iterator for IterAllChunks<T> {
+    type Item = IterAll<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.starts.next()?;
+        let end = start.saturating_add(self.size).min(T::INHABITANTS);
+        Some(IterAll::new(start..end))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
     }
 }
 
-impl<T: ?Sized> Finite for std::marker::PhantomData<T> {
+impl<T: Finite> ExactSizeIterator for IterAllChunks<T> {}
+
+impl<T: ?Sized> Finite for PhantomData<T> {
     const INHABITANTS: usize = 1;
 
     fn to_usize(&self) -> usize {
@@ -121,6 +644,7 @@ macro_rules! impl_uprim {
 
 impl_uprim!(u8);
 impl_uprim!(u16);
+#[cfg(target_pointer_width = "64")]
 impl_uprim!(u32);
 
 macro_rules! impl_iprim {
@@ -141,12 +665,13 @@ macro_rules! impl_iprim {
 
 impl_iprim!(i8, u8);
 impl_iprim!(i16, u16);
+#[cfg(target_pointer_width = "64")]
 impl_iprim!(i32, u32);
 
 macro_rules! impl_unonzero {
     ($type:path) => {
         impl Finite for $type {
-            const INHABITANTS: usize = 2usize.pow(std::mem::size_of::<$type>() as u32 * 8) - 1;
+            const INHABITANTS: usize = <$type>::MAX.get() as usize;
 
             fn to_usize(&self) -> usize {
                 self.get() as usize - 1
@@ -181,20 +706,40 @@ macro_rules! impl_inonzero {
 
 impl_inonzero!(NonZeroI8, i8);
 impl_inonzero!(NonZeroI16, i16);
-impl_inonzero!(NonZeroI32, i32);
+
+// Implemented without going through `i32`, which isn't `Finite` on 32-bit targets.
+impl Finite for NonZeroI32 {
+    const INHABITANTS: usize = u32::MAX as usize;
+
+    fn to_usize(&self) -> usize {
+        (self.get() as u32 - 1) as usize
+    }
+
+    fn from_usize(i: usize) -> Option<Self> {
+        if i < Self::INHABITANTS {
+            Self::new((i as u32 + 1) as i32)
+        } else {
+            None
+        }
+    }
+}
 
 const CHAR_GAP_START: usize = 0xD800;
 const CHAR_GAP_END: usize = 0xDFFF;
 const CHAR_GAP_SIZE: usize = CHAR_GAP_END - CHAR_GAP_START + 1;
+pub(crate) const fn char_to_usize(c: char) -> usize {
+    let mut v = c as usize;
+    if v > CHAR_GAP_END {
+        v -= CHAR_GAP_SIZE;
+    }
+    v
+}
+
 impl Finite for char {
     const INHABITANTS: usize = char::MAX as usize + 1 - CHAR_GAP_SIZE;
 
     fn to_usize(&self) -> usize {
-        let mut v = *self as usize;
-        if v > CHAR_GAP_END {
-            v -= CHAR_GAP_SIZE;
-        }
-        v
+        char_to_usize(*self)
     }
 
     fn from_usize(mut i: usize) -> Option<Self> {
@@ -205,6 +750,7 @@ impl Finite for char {
     }
 }
 
+#[cfg(target_pointer_width = "64")]
 impl Finite for f32 {
     const INHABITANTS: usize = u32::INHABITANTS;
 
@@ -217,6 +763,7 @@ impl Finite for f32 {
     }
 }
 
+#[cfg(target_pointer_width = "64")]
 macro_rules! impl_from {
     ($type:path, $from:path) => {
         impl Finite for $type {
@@ -233,10 +780,37 @@ macro_rules! impl_from {
     };
 }
 
+#[cfg(target_pointer_width = "64")]
 impl_from!(std::net::Ipv4Addr, u32);
 
+/// An array with too many inhabitants results in a compile error when used:
+/// ```compile_fail
+/// use exhaustive_map::{Finite, FiniteExt};
+///
+/// let count = <[u32; 3]>::iter_all().count();
+/// ```
 impl<const N: usize, T: Finite> Finite for [T; N] {
-    const INHABITANTS: usize = T::INHABITANTS.pow(N as u32);
+    const INHABITANTS: usize = {
+        // Avoids looping `N` times when the result doesn't depend on `N`.
+        if N == 0 || T::INHABITANTS == 1 {
+            1
+        } else if T::INHABITANTS == 0 {
+            0
+        } else {
+            let mut res: usize = 1;
+            let mut k = 0;
+            while k < N {
+                res = match res.checked_mul(T::INHABITANTS) {
+                    Some(v) => v,
+                    None => {
+                        panic!("array of `T` with `N` elements exceeds `usize::MAX` inhabitants")
+                    }
+                };
+                k += 1;
+            }
+            res
+        }
+    };
 
     fn to_usize(&self) -> usize {
         let mut res = 0;
@@ -261,7 +835,7 @@ impl<const N: usize, T: Finite> Finite for [T; N] {
     }
 }
 
-__impl_tuples!(16);
+__impl_tuples!(20);
 
 macro_rules! impl_deref {
     ($type:path) => {
@@ -296,19 +870,19 @@ impl<'a, T: Finite + Clone> Finite for Cow<'a, T> {
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::convert::Infallible)]
+#[finite(foreign = std::convert::Infallible)]
 enum _Infallible {}
 
 #[derive(Finite)]
-#[__finite_foreign(std::alloc::System)]
+#[finite(foreign = std::alloc::System)]
 struct _System;
 
 #[derive(Finite)]
-#[__finite_foreign(std::marker::PhantomPinned)]
+#[finite(foreign = std::marker::PhantomPinned)]
 struct _PhantomPinned;
 
 #[derive(Finite)]
-#[__finite_foreign(std::cmp::Ordering)]
+#[finite(foreign = std::cmp::Ordering)]
 enum _Ordering {
     Less,
     Equal,
@@ -316,7 +890,7 @@ enum _Ordering {
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::net::Shutdown)]
+#[finite(foreign = std::net::Shutdown)]
 enum _Shutdown {
     Read,
     Write,
@@ -324,7 +898,7 @@ enum _Shutdown {
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::num::FpCategory)]
+#[finite(foreign = std::num::FpCategory)]
 enum _FpCategory {
     Nan,
     Infinite,
@@ -334,21 +908,21 @@ enum _FpCategory {
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::sync::mpsc::RecvTimeoutError)]
+#[finite(foreign = std::sync::mpsc::RecvTimeoutError)]
 enum _RecvTimeoutError {
     Timeout,
     Disconnected,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::sync::mpsc::TryRecvError)]
+#[finite(foreign = std::sync::mpsc::TryRecvError)]
 enum _TryRecvError {
     Empty,
     Disconnected,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::fmt::Alignment)]
+#[finite(foreign = std::fmt::Alignment)]
 enum _Alignment {
     Left,
     Right,
@@ -356,28 +930,28 @@ enum _Alignment {
 }
 
 #[derive(Finite)]
-#[__finite_foreign(Option)]
+#[finite(foreign = Option)]
 enum _Option<T> {
     None,
     Some(T),
 }
 
 #[derive(Finite)]
-#[__finite_foreign(Result)]
+#[finite(foreign = Result)]
 enum _Result<T, E> {
     Ok(T),
     Err(E),
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::task::Poll)]
+#[finite(foreign = std::task::Poll)]
 enum _Poll<T> {
     Ready(T),
     Pending,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::Bound)]
+#[finite(foreign = std::ops::Bound)]
 enum _Bound<T> {
     Included(T),
     Excluded(T),
@@ -385,41 +959,67 @@ enum _Bound<T> {
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::ControlFlow)]
+#[finite(foreign = std::ops::ControlFlow)]
 enum _ControlFlow<B, C> {
     Continue(C),
     Break(B),
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::Range)]
+#[finite(foreign = std::ops::Range)]
 struct _Range<Idx> {
     start: Idx,
     end: Idx,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::RangeFrom)]
+#[finite(foreign = std::ops::RangeFrom)]
 struct _RangeFrom<Idx> {
     start: Idx,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::RangeTo)]
+#[finite(foreign = std::ops::RangeTo)]
 struct _RangeTo<Idx> {
     end: Idx,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::RangeToInclusive)]
+#[finite(foreign = std::ops::RangeToInclusive)]
 struct _RangeToInclusive<Idx> {
     end: Idx,
 }
 
 #[derive(Finite)]
-#[__finite_foreign(std::ops::RangeFull)]
+#[finite(foreign = std::ops::RangeFull)]
 struct _RangeFull;
 
+#[derive(Finite)]
+#[finite(foreign = std::num::Wrapping)]
+struct _Wrapping<T>(T);
+
+#[derive(Finite)]
+#[finite(foreign = std::num::Saturating)]
+struct _Saturating<T>(T);
+
+/// Encoded the same way as `(start, end)`.
+///
+/// A `RangeInclusive` has internal state keeping track of whether it has been exhausted
+/// by iteration. This state is ignored by [`to_usize`](Finite::to_usize) and
+/// [`from_usize`](Finite::from_usize) always returns a non-exhausted range `start..=end`.
+impl<Idx: Finite> Finite for std::ops::RangeInclusive<Idx> {
+    const INHABITANTS: usize = <(Idx, Idx)>::INHABITANTS;
+
+    fn to_usize(&self) -> usize {
+        self.end().to_usize() * Idx::INHABITANTS + self.start().to_usize()
+    }
+
+    fn from_usize(i: usize) -> Option<Self> {
+        let (start, end) = <(Idx, Idx)>::from_usize(i)?;
+        Some(start..=end)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -457,6 +1057,179 @@ mod test {
         }
     }
 
+    #[cfg(target_pointer_width = "64")]
+    const _: () = assert_fits::<(u16, u16, bool)>();
+
+    #[test]
+    fn test_fits_in_usize() {
+        assert!(fits_in_usize(0));
+        assert!(fits_in_usize(u8::INHABITANTS as u128));
+        assert!(!fits_in_usize(u64::MAX as u128 + 1));
+        assert!(!fits_in_usize(u128::MAX));
+    }
+
+    #[test]
+    fn test_iter_all_rev() {
+        let all: Vec<_> = Option::<bool>::iter_all().rev().collect();
+        assert_eq!(all, [Some(true), Some(false), None]);
+    }
+
+    #[test]
+    fn test_iter_all_from() {
+        assert_eq!(u8::iter_all_from(250).count(), 6);
+        assert_eq!(u8::iter_all_from(250).next(), Some(250));
+        assert_eq!(u8::iter_all_from(256).count(), 0);
+        assert_eq!(u8::iter_all_from(usize::MAX).count(), 0);
+        assert_eq!(bool::iter_all_from(0).collect::<Vec<_>>(), [false, true]);
+    }
+
+    #[test]
+    fn test_iter_all_skips_from_usize() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static CALLS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Counting(u8);
+
+        impl Finite for Counting {
+            const INHABITANTS: usize = u8::INHABITANTS;
+
+            fn to_usize(&self) -> usize {
+                self.0.to_usize()
+            }
+
+            fn from_usize(i: usize) -> Option<Self> {
+                CALLS.set(CALLS.get() + 1);
+                u8::from_usize(i).map(Self)
+            }
+        }
+
+        assert_eq!(Counting::iter_all().count(), 256);
+        assert_eq!(CALLS.get(), 0);
+        assert_eq!(Counting::iter_all().last(), Some(Counting(255)));
+        assert_eq!(CALLS.get(), 1);
+        assert_eq!(Counting::iter_all().nth(100), Some(Counting(100)));
+        assert_eq!(CALLS.get(), 2);
+        assert_eq!(Counting::iter_all().nth_back(5), Some(Counting(250)));
+        assert_eq!(CALLS.get(), 3);
+        assert_eq!(Counting::iter_all_from(256).last(), None);
+        assert_eq!(CALLS.get(), 3);
+    }
+
+    #[test]
+    fn test_iter_all_refs() {
+        let all: Vec<_> = u8::iter_all().collect();
+        let mut it = u8::iter_all();
+        it.nth(9);
+        it.nth_back(9);
+        let refs: Vec<_> = it.refs(&all).collect();
+        assert_eq!(refs.len(), 236);
+        assert!(std::ptr::eq(refs[0], &all[10]));
+        assert!(std::ptr::eq(refs[235], &all[245]));
+    }
+
+    #[test]
+    #[should_panic = "the slice must contain all inhabitants"]
+    fn test_iter_all_refs_wrong_len() {
+        let _ = u8::iter_all().refs(&[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_iter_all_remaining() {
+        let mut iter = Option::<bool>::iter_all();
+        assert_eq!(iter.remaining(), 0..3);
+        iter.next();
+        assert_eq!(iter.remaining(), 1..3);
+        iter.next_back();
+        assert_eq!(iter.remaining(), 1..2);
+        assert_eq!(iter.remaining().len(), iter.len());
+        iter.next();
+        assert!(iter.remaining().is_empty());
+        let mut iter = <(u8, u8)>::iter_all();
+        iter.nth(299);
+        assert_eq!(iter.remaining(), 300..65536);
+    }
+
+    #[test]
+    fn test_iter_all_indexed() {
+        #[derive(Finite, Debug, PartialEq)]
+        enum Key {
+            A,
+            B(bool),
+            C,
+        }
+
+        let all: Vec<_> = Key::iter_all_indexed().collect();
+        assert_eq!(
+            all,
+            [
+                (0, Key::A),
+                (1, Key::B(false)),
+                (2, Key::B(true)),
+                (3, Key::C)
+            ]
+        );
+        for (i, v) in all {
+            assert_eq!(v.to_usize(), i);
+        }
+        assert_eq!(Key::iter_all_indexed().len(), 4);
+    }
+
+    #[test]
+    fn test_iter_all_chunks() {
+        let chunks: Vec<Vec<_>> = u8::iter_all_chunks(100).map(|c| c.collect()).collect();
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [100, 100, 56]
+        );
+        assert_eq!(chunks[0][0], 0);
+        assert_eq!(chunks[1][0], 100);
+        assert_eq!(chunks[2][0], 200);
+        assert_eq!(chunks.concat(), u8::iter_all().collect::<Vec<_>>());
+
+        assert_eq!(u8::iter_all_chunks(256).len(), 1);
+        assert_eq!(u8::iter_all_chunks(usize::MAX).len(), 1);
+        assert_eq!(std::convert::Infallible::iter_all_chunks(1).len(), 0);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_from_rng() {
+        use rand::SeedableRng;
+
+        #[derive(Finite, Debug, PartialEq)]
+        enum Enum {
+            A,
+            B(bool),
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let v = Enum::from_rng(&mut rng);
+            seen[v.to_usize()] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn test_from_rng_uninhabited() {
+        crate::InRange::<3, 3>::from_rng(&mut rand::thread_rng());
+    }
+
+    #[test]
+    fn test_inhabitants_in_const_context() {
+        const N: usize = <(bool, Option<u8>)>::INHABITANTS;
+        let arr = [(); <(bool, Option<u8>)>::INHABITANTS];
+        assert_eq!(arr.len(), N);
+        assert_eq!(N, 2 * 257);
+    }
+
     #[test]
     fn test_infallible() {
         test_all::<std::convert::Infallible>(0);
@@ -482,6 +1255,7 @@ mod test {
         test_all::<u16>(256 * 256);
     }
 
+    #[cfg(target_pointer_width = "64")]
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn test_u32() {
@@ -498,6 +1272,7 @@ mod test {
         test_all::<i16>(256 * 256);
     }
 
+    #[cfg(target_pointer_width = "64")]
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn test_i32() {
@@ -517,7 +1292,7 @@ mod test {
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn test_nonzero_u32() {
-        test_all::<NonZeroU32>(256 * 256 * 256 * 256 - 1);
+        test_all::<NonZeroU32>(u32::MAX as usize);
     }
 
     #[test]
@@ -533,7 +1308,18 @@ mod test {
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn test_nonzero_i32() {
-        test_all::<NonZeroI32>(256 * 256 * 256 * 256 - 1);
+        test_all::<NonZeroI32>(u32::MAX as usize);
+    }
+
+    #[test]
+    fn test_nonzero_i32_encoding() {
+        let v = |i| NonZeroI32::new(i).unwrap();
+        assert_eq!(v(1).to_usize(), 0);
+        assert_eq!(v(i32::MAX).to_usize(), i32::MAX as usize - 1);
+        assert_eq!(v(i32::MIN).to_usize(), i32::MAX as usize);
+        assert_eq!(v(-1).to_usize(), u32::MAX as usize - 1);
+        assert_eq!(NonZeroI32::from_usize(u32::MAX as usize - 1), Some(v(-1)));
+        assert_eq!(NonZeroI32::from_usize(u32::MAX as usize), None);
     }
 
     #[test]
@@ -541,6 +1327,13 @@ mod test {
         test_all::<char>(0x110000 - CHAR_GAP_SIZE);
     }
 
+    #[test]
+    fn test_char_range() {
+        test_all::<crate::CharRangeInclusive<'a', 'z'>>(26);
+        test_all::<crate::AsciiChar>(128);
+    }
+
+    #[cfg(target_pointer_width = "64")]
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn test_f32() {
@@ -567,6 +1360,13 @@ mod test {
         test_all::<[(); 100]>(1);
     }
 
+    #[test]
+    fn test_huge_arr() {
+        assert_eq!(<[(); usize::MAX]>::INHABITANTS, 1);
+        assert_eq!(<[std::convert::Infallible; usize::MAX]>::INHABITANTS, 0);
+        assert_eq!(<[u8; 0]>::INHABITANTS, 1);
+    }
+
     #[test]
     fn test_tuple_u8_bool() {
         test_all::<(u8, bool)>(512);
@@ -577,11 +1377,59 @@ mod test {
         test_all::<(bool, u8)>(512);
     }
 
+    #[test]
+    fn test_tuple_20() {
+        // std doesn't implement `Debug` and `PartialEq` for tuples this large,
+        // so we can't use `test_all` here.
+        #[rustfmt::skip]
+        type Tuple20 = (
+            bool, bool, bool, bool, bool, bool, bool, bool, bool, bool,
+            bool, bool, bool, bool, bool, bool, bool, bool, bool, bool,
+        );
+        assert_eq!(Tuple20::INHABITANTS, 2usize.pow(20));
+
+        for i in (0..Tuple20::INHABITANTS).step_by(997) {
+            let v = Tuple20::from_usize(i).unwrap();
+            assert_eq!(v.to_usize(), i);
+        }
+        assert!(Tuple20::from_usize(Tuple20::INHABITANTS).is_none());
+
+        let v: Tuple20 = (
+            true, false, false, false, false, false, false, false, false, false, false, false,
+            false, false, false, false, false, false, false, true,
+        );
+        assert_eq!(v.to_usize(), 1 + 2usize.pow(19));
+    }
+
     #[test]
     fn test_cow_arr() {
         test_all::<Cow<[bool; 2]>>(4);
     }
 
+    #[test]
+    fn test_wrapping_and_saturating() {
+        use std::num::{Saturating, Wrapping};
+
+        test_all::<Wrapping<u8>>(256);
+        test_all::<Saturating<Option<bool>>>(3);
+        assert_eq!(Wrapping(7u8).to_usize(), 7);
+    }
+
+    #[test]
+    fn test_wrappers_with_nonzero() {
+        use std::num::{Saturating, Wrapping};
+
+        test_all::<(Wrapping<u8>, NonZeroU8)>(256 * 255);
+        test_all::<(NonZeroI8, Saturating<bool>)>(255 * 2);
+
+        let mut m = crate::ExhaustiveMap::<(Wrapping<u8>, NonZeroU8), u32>::default();
+        assert_eq!(m.len(), 256 * 255);
+        let k = (Wrapping(200), NonZeroU8::new(3).unwrap());
+        m[k] = 1;
+        assert_eq!(k.to_usize(), 200 + 256 * 2);
+        assert_eq!(m.values().sum::<u32>(), 1);
+    }
+
     #[test]
     fn test_tuple_and_arr_same_encoding() {
         let i1 = [1u8, 2u8].to_usize();
@@ -589,17 +1437,42 @@ mod test {
         assert_eq!(i1, i2);
     }
 
+    #[cfg(target_pointer_width = "64")]
     #[test]
     #[cfg_attr(debug_assertions, ignore)]
     fn test_ipv4_address() {
         test_all::<std::net::Ipv4Addr>(256usize.pow(4));
     }
 
+    #[test]
+    fn test_nested_option_order() {
+        test_all::<Option<Option<bool>>>(4);
+        let expected = [None, Some(None), Some(Some(false)), Some(Some(true))];
+        for (i, v) in expected.into_iter().enumerate() {
+            assert_eq!(v.to_usize(), i);
+            assert_eq!(Option::<Option<bool>>::from_usize(i), Some(v));
+        }
+    }
+
     #[test]
     fn test_std_cmp_ordering() {
         test_all::<std::cmp::Ordering>(3);
     }
 
+    #[test]
+    fn test_range_inclusive() {
+        test_all::<std::ops::RangeInclusive<bool>>(4);
+        test_all::<std::ops::RangeInclusive<u8>>(256 * 256);
+
+        let range = (3u8..=7).to_usize();
+        assert_eq!(range, (3u8, 7u8).to_usize());
+
+        let mut exhausted = 3u8..=3;
+        exhausted.next();
+        assert!(exhausted.is_empty());
+        assert_eq!(exhausted.to_usize(), (3u8..=3).to_usize());
+    }
+
     #[test]
     fn test_derive_unit_struct() {
         #[derive(Finite, Debug, PartialEq)]
@@ -746,6 +1619,43 @@ mod test {
         test_all::<Enum>(2usize.pow(4));
     }
 
+    #[test]
+    #[allow(non_camel_case_types)]
+    fn test_derive_with_unit_structs_named_like_temporaries() {
+        #[derive(Finite, Debug, PartialEq)]
+        struct v;
+        #[derive(Finite, Debug, PartialEq)]
+        struct i;
+        #[derive(Finite, Debug, PartialEq)]
+        struct res;
+        #[derive(Finite, Debug, PartialEq)]
+        struct v_0;
+        #[derive(Finite, Debug, PartialEq)]
+        struct field_v;
+
+        #[derive(Finite, Debug, PartialEq)]
+        struct Tuple(v, i, bool, res, v_0, field_v);
+        test_all::<Tuple>(2);
+
+        #[derive(Finite, Debug, PartialEq)]
+        struct Named {
+            v: v,
+            i: bool,
+            res: res,
+            field_0: bool,
+            field_v: field_v,
+        }
+        test_all::<Named>(4);
+
+        #[derive(Finite, Debug, PartialEq)]
+        enum Enum {
+            A(bool, v_0, i),
+            B { v: bool, field_v: field_v, i: i },
+            C,
+        }
+        test_all::<Enum>(5);
+    }
+
     #[test]
     fn test_derive_generic() {
         #[derive(Finite, Debug, PartialEq)]
@@ -755,6 +1665,238 @@ mod test {
         test_all::<Generic<u8>>(257);
     }
 
+    #[test]
+    fn test_derive_const_generic() {
+        #[derive(Finite, Debug, PartialEq)]
+        struct ConstGeneric<const N: usize> {
+            data: [u8; N],
+        }
+        test_all::<ConstGeneric<0>>(1);
+        test_all::<ConstGeneric<2>>(256 * 256);
+    }
+
+    #[test]
+    fn test_derive_const_and_type_generic() {
+        #[derive(Finite, Debug, PartialEq)]
+        enum Mixed<T, const N: usize> {
+            _A([T; N]),
+            _B(Option<T>),
+        }
+        test_all::<Mixed<bool, 3>>(8 + 3);
+    }
+
+    #[test]
+    fn test_derive_field_order() {
+        #[derive(Finite, Debug, PartialEq)]
+        struct Named {
+            a: bool,
+            b: Option<bool>,
+            c: u8,
+        }
+
+        #[derive(Finite, Debug, PartialEq)]
+        struct Unnamed(bool, Option<bool>, u8);
+
+        #[derive(Finite, Debug, PartialEq)]
+        enum Enum {
+            A,
+            B(bool, Option<bool>, u8),
+            C { a: bool, b: Option<bool>, c: u8 },
+        }
+
+        for a in bool::iter_all() {
+            for b in Option::<bool>::iter_all() {
+                for c in u8::iter_all() {
+                    let expected = a.to_usize() + 2 * (b.to_usize() + 3 * c.to_usize());
+                    assert_eq!((a, b, c).to_usize(), expected);
+                    assert_eq!(Named { a, b, c }.to_usize(), expected);
+                    assert_eq!(Unnamed(a, b, c).to_usize(), expected);
+                    assert_eq!(Enum::B(a, b, c).to_usize(), 1 + expected);
+                    assert_eq!(Enum::C { a, b, c }.to_usize(), 1 + 6 * 256 + expected);
+                }
+            }
+        }
+        assert_eq!(
+            Named::from_usize(1 + 2 * 2),
+            Some(Named {
+                a: true,
+                b: Some(true),
+                c: 0
+            })
+        );
+        assert_eq!(
+            Unnamed::from_usize(2 * 3 * 255),
+            Some(Unnamed(false, None, 255))
+        );
+    }
+
+    #[test]
+    fn test_derive_const_finite() {
+        #[derive(Finite, Debug, PartialEq)]
+        #[finite(const_fns)]
+        enum Color {
+            Red,
+            Green,
+            Blue = 10,
+        }
+
+        const INDICES: [usize; 3] = [
+            Color::Red.to_usize_const(),
+            Color::Green.to_usize_const(),
+            Color::Blue.to_usize_const(),
+        ];
+        const VALUES: [Option<Color>; 4] = [
+            Color::from_usize_const(0),
+            Color::from_usize_const(1),
+            Color::from_usize_const(2),
+            Color::from_usize_const(3),
+        ];
+        assert_eq!(INDICES, [0, 1, 2]);
+        assert_eq!(
+            VALUES,
+            [
+                Some(Color::Red),
+                Some(Color::Green),
+                Some(Color::Blue),
+                None
+            ]
+        );
+        for c in Color::iter_all() {
+            assert_eq!(c.to_usize_const(), c.to_usize());
+        }
+
+        // Without the option, existing methods with the same names don't conflict.
+        #[derive(Finite)]
+        enum Existing {
+            A,
+        }
+
+        impl Existing {
+            fn to_usize_const(&self) -> &'static str {
+                "existing"
+            }
+        }
+
+        assert_eq!(Existing::A.to_usize_const(), "existing");
+    }
+
+    #[test]
+    fn test_derive_phantom_generic() {
+        #[derive(Debug, PartialEq)]
+        struct NotFinite;
+
+        #[derive(Finite, Debug, PartialEq)]
+        struct Phantom<T>(PhantomData<T>);
+        test_all::<Phantom<NotFinite>>(1);
+
+        #[derive(Finite, Debug, PartialEq)]
+        enum Mixed<T, U> {
+            _A(T),
+            _B(PhantomData<U>),
+        }
+        test_all::<Mixed<bool, NotFinite>>(3);
+    }
+
+    #[test]
+    fn test_derive_phantom_variance() {
+        const fn assert_send_sync<T: Send + Sync>() {}
+
+        #[derive(Finite, Debug, PartialEq)]
+        struct Covariant<T>(PhantomData<fn() -> T>);
+        #[derive(Finite, Debug, PartialEq)]
+        struct Contravariant<T>(PhantomData<fn(T)>);
+        #[derive(Finite, Debug, PartialEq)]
+        struct ConstPtr<T>(PhantomData<*const T>);
+        #[derive(Finite, Debug, PartialEq)]
+        struct MutRef<'a, T>(PhantomData<&'a mut T>);
+
+        test_all::<Covariant<String>>(1);
+        test_all::<Contravariant<String>>(1);
+        test_all::<ConstPtr<String>>(1);
+        test_all::<MutRef<String>>(1);
+
+        // The map is `Send` and `Sync` regardless of the key.
+        const _: () = assert_send_sync::<crate::ExhaustiveMap<ConstPtr<u8>, u8>>();
+        const _: () = assert_send_sync::<crate::ExhaustiveMap<MutRef<'static, u8>, u8>>();
+        const _: () = assert_send_sync::<crate::ExhaustiveMap<Covariant<*mut u8>, u8>>();
+    }
+
+    #[test]
+    fn test_derive_max_inhabitants() {
+        #[derive(Finite, Debug, PartialEq)]
+        #[finite(max_inhabitants = 6)]
+        enum Key {
+            A(bool),
+            B(Option<bool>),
+        }
+        test_all::<Key>(5);
+
+        #[derive(Finite, Debug, PartialEq)]
+        #[finite(max_inhabitants = 10)]
+        struct Generic<T>(T);
+        test_all::<Generic<Option<bool>>>(3);
+    }
+
+    #[test]
+    fn test_derive_phantom_self() {
+        #[derive(Finite, Debug, PartialEq)]
+        struct Marked(PhantomData<Marked>, bool);
+        test_all::<Marked>(2);
+    }
+
+    #[test]
+    fn test_derive_in_range_field() {
+        use crate::{InRange, InRangeBounds};
+
+        #[derive(Finite, Debug, PartialEq)]
+        struct Key {
+            a: InRange<2, 5>,
+            b: bool,
+        }
+        test_all::<Key>(6);
+
+        let key = Key {
+            a: InRange::new(4).unwrap(),
+            b: true,
+        };
+        assert_eq!(key.to_usize(), 2 + 3);
+        assert_eq!(Key::from_usize(5), Some(key));
+    }
+
+    #[test]
+    fn test_derive_variant_index() {
+        #[derive(Finite, Debug, PartialEq)]
+        #[finite(variant_index)]
+        enum Key {
+            A,
+            B(bool),
+            C { x: u8, y: Option<bool> },
+        }
+        assert_eq!(Key::A.variant_index(), 0);
+        assert_eq!(Key::B(true).variant_index(), 1);
+        for x in [0, 100, 255] {
+            for y in [None, Some(false), Some(true)] {
+                assert_eq!(Key::C { x, y }.variant_index(), 2);
+            }
+        }
+        assert_eq!(Key::C { x: 1, y: None }.to_usize(), 4);
+
+        #[derive(Finite)]
+        #[finite(variant_index)]
+        enum Generic<T> {
+            A(T),
+            B,
+        }
+        const B: usize = Generic::<bool>::B.variant_index();
+        assert_eq!(B, 1);
+        assert_eq!(Generic::A(false).variant_index(), 0);
+
+        #[derive(Finite, Debug, PartialEq)]
+        #[finite(variant_index)]
+        enum Empty {}
+        test_all::<Empty>(0);
+    }
+
     #[test]
     fn test_derive_generic_lifetime() {
         #[derive(Finite, Debug, PartialEq)]
@@ -763,4 +1905,42 @@ mod test {
         }
         test_all::<Lifetime>(1);
     }
+
+    #[test]
+    fn test_derive_foreign() {
+        mod foreign {
+            #[derive(Debug, PartialEq)]
+            pub enum Key {
+                A,
+                B(bool),
+                C { x: Option<bool>, y: bool },
+            }
+
+            #[derive(Debug, PartialEq)]
+            pub struct Wrapper<T>(pub T, pub bool);
+        }
+
+        #[derive(Finite)]
+        #[finite(foreign = foreign::Key)]
+        enum _Key {
+            A,
+            B(bool),
+            C { x: Option<bool>, y: bool },
+        }
+
+        #[derive(Finite)]
+        #[finite(foreign = foreign::Wrapper, max_inhabitants = 100)]
+        struct _Wrapper<T>(T, bool);
+
+        test_all::<foreign::Key>(9);
+        assert_eq!(foreign::Key::B(true).to_usize(), 2);
+        assert_eq!(
+            foreign::Key::from_usize(8),
+            Some(foreign::Key::C {
+                x: Some(true),
+                y: true
+            })
+        );
+        test_all::<foreign::Wrapper<foreign::Key>>(18);
+    }
 }
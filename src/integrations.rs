@@ -0,0 +1,122 @@
+/// Implements [`Finite`](crate::Finite) for a flags type generated by the
+/// [`bitflags!`](https://docs.rs/bitflags/2/bitflags/macro.bitflags.html) macro.
+///
+/// The second argument must be the type of the underlying bits.
+/// Every bit pattern is treated as an inhabitant, including unknown bits,
+/// so the number of inhabitants is the same as for the bits type.
+///
+/// ```
+/// use bitflags::bitflags;
+/// use exhaustive_map::{impl_finite_bitflags, Finite};
+///
+/// bitflags! {
+///     #[derive(Debug, PartialEq)]
+///     struct Flags: u8 {
+///         const A = 0b01;
+///         const B = 0b10;
+///     }
+/// }
+///
+/// impl_finite_bitflags!(Flags, u8);
+///
+/// assert_eq!(Flags::INHABITANTS, 256);
+/// assert_eq!((Flags::A | Flags::B).to_usize(), 3);
+/// assert_eq!(Flags::from_usize(2), Some(Flags::B));
+/// ```
+#[cfg(feature = "bitflags")]
+#[macro_export]
+macro_rules! impl_finite_bitflags {
+    ($type:ty, $bits:ty) => {
+        impl $crate::Finite for $type {
+            const INHABITANTS: usize = <$bits as $crate::Finite>::INHABITANTS;
+
+            fn to_usize(&self) -> usize {
+                <$bits as $crate::Finite>::to_usize(&self.bits())
+            }
+
+            fn from_usize(i: usize) -> Option<Self> {
+                <$bits as $crate::Finite>::from_usize(i).map(Self::from_bits_retain)
+            }
+        }
+    };
+}
+
+/// A [`proptest`] strategy generating inhabitants of `T` uniformly at random.
+///
+/// Requires the `proptest` feature.
+///
+/// ```
+/// use exhaustive_map::{finite_strategy, Finite};
+/// use proptest::prelude::*;
+///
+/// #[derive(Finite, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// proptest!(|(color in finite_strategy::<Color>())| {
+///     prop_assert_eq!(Color::from_usize(color.to_usize()), Some(color));
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn finite_strategy<T: crate::Finite + std::fmt::Debug>(
+) -> impl proptest::strategy::Strategy<Value = T> {
+    use proptest::strategy::Strategy;
+
+    (0..T::INHABITANTS).prop_map(crate::finite::nth_inhabitant)
+}
+
+#[cfg(all(test, feature = "bitflags"))]
+mod test_bitflags {
+    use bitflags::bitflags;
+
+    use crate::{Finite, FiniteExt};
+
+    bitflags! {
+        #[derive(Debug, PartialEq)]
+        struct Flags: u8 {
+            const A = 1 << 0;
+            const B = 1 << 1;
+            const C = 1 << 7;
+        }
+    }
+
+    impl_finite_bitflags!(Flags, u8);
+
+    #[test]
+    fn test_bitflags() {
+        assert_eq!(Flags::INHABITANTS, 256);
+        for (i, flags) in Flags::iter_all().enumerate() {
+            assert_eq!(flags.bits() as usize, i);
+            assert_eq!(flags.to_usize(), i);
+        }
+        assert_eq!((Flags::A | Flags::C).to_usize(), 0b1000_0001);
+        assert_eq!(Flags::from_usize(256), None);
+    }
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod test_proptest {
+    use proptest::prelude::*;
+
+    use super::finite_strategy;
+    use crate::Finite;
+
+    #[derive(Finite, Debug, PartialEq)]
+    enum Key {
+        A,
+        B(bool),
+        C { x: u8, y: Option<bool> },
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(key in finite_strategy::<Key>()) {
+            let i = key.to_usize();
+            prop_assert!(i < Key::INHABITANTS);
+            prop_assert_eq!(Key::from_usize(i), Some(key));
+        }
+    }
+}
@@ -1,12 +1,37 @@
 #![doc = include_str!("../README.md")]
 #![deny(clippy::undocumented_unsafe_blocks)]
 
+mod array_map;
+mod bit_array;
 mod finite;
+mod integrations;
 mod map;
 mod range;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "serde")]
+pub mod serde_indexed;
+mod transition_table;
 
-pub use finite::{Finite, FiniteExt, IterAll};
-pub use map::{ExhaustiveMap, IntoIter, IntoValues, Iter, IterMut, Values, ValuesMut};
-pub use range::{InRange, InRangeBounds, InRangeInclusive};
+pub use array_map::ExhaustiveArrayMap;
+pub use bit_array::BitArray;
+#[doc(hidden)]
+pub use finite::{__inhabitants_add, __inhabitants_mul};
+pub use finite::{
+    assert_fits, fits_in_usize, ConstFinite, Finite, FiniteExt, IterAll, IterAllChunks,
+    IterAllIndexed,
+};
+#[cfg(feature = "proptest")]
+pub use integrations::finite_strategy;
+pub use map::{
+    cartesian_product, cmp_table, Diff, EntriesIndexed, EnumerateValuesMut, ExhaustiveMap,
+    IntoIter, IntoValues, Iter, IterMut, KeyedIter, KeyedIterMut, KeyedMap, LengthError,
+    OutOfBounds, Pairs, PermutationError, Project, Values, ValuesMut,
+};
+pub use range::{
+    AsciiChar, CharRangeInclusive, InRange, InRangeBounds, InRangeI, InRangeInclusive,
+    InRangeInclusiveI, Interval, Quantized,
+};
+pub use transition_table::TransitionTable;
 
 extern crate self as exhaustive_map;
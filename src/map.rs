@@ -1,19 +1,24 @@
 use std::{
     borrow::Borrow,
     collections::{BTreeMap, HashMap},
-    fmt::Debug,
+    error::Error,
+    fmt::{Debug, Display},
     hash::Hash,
+    iter::{Product, Sum},
     marker::PhantomData,
     mem::MaybeUninit,
     ops::{Index, IndexMut},
 };
 
 use crate::{
-    finite::{Finite, FiniteExt},
-    IterAll,
+    finite::{nth_inhabitant, Finite, FiniteExt},
+    BitArray, IterAll,
 };
 
 /// A map which is guaranteed to always contain a value for each possible key of type `K`.
+///
+/// Key types with zero or one inhabitants are supported,
+/// e.g. a map with [`Infallible`](std::convert::Infallible) keys is always empty.
 /// ```
 /// use exhaustive_map::ExhaustiveMap;
 ///
@@ -29,6 +34,31 @@ use crate::{
 /// assert_eq!(map[3], 9999);
 /// assert_eq!(map[7], 103);
 /// ```
+///
+/// # Truth tables
+///
+/// With keys of type `[bool; N]`, a map is a complete truth table for a boolean function
+/// of `N` inputs, with the rows ordered with the first input changing fastest:
+/// ```
+/// use exhaustive_map::ExhaustiveMap;
+///
+/// let and = ExhaustiveMap::<[bool; 2], bool>::from_fn(|[a, b]| a && b);
+/// let or = ExhaustiveMap::<[bool; 2], bool>::from_fn(|[a, b]| a || b);
+/// assert_eq!(
+///     and.values().collect::<Vec<_>>(),
+///     [&false, &false, &false, &true]
+/// );
+/// assert_eq!(
+///     or.values().collect::<Vec<_>>(),
+///     [&false, &true, &true, &true]
+/// );
+///
+/// // Multiple outputs of the same inputs, here a full adder returning `[sum, carry]`:
+/// let adder = ExhaustiveMap::<[bool; 3], [bool; 2]>::from_fn(|[a, b, c]| {
+///     [a ^ b ^ c, (a && b) || (c && (a ^ b))]
+/// });
+/// assert_eq!(adder[[true, false, true]], [false, true]);
+/// ```
 #[repr(transparent)]
 pub struct ExhaustiveMap<K: Finite, V> {
     // Replace with [V; { K::INHABITANTS }] when Rust supports it
@@ -37,6 +67,16 @@ pub struct ExhaustiveMap<K: Finite, V> {
 }
 
 impl<K: Finite, V> ExhaustiveMap<K, V> {
+    /// The number of elements in any map of this type, equal to `K::INHABITANTS`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let array = [0u8; ExhaustiveMap::<Option<bool>, u8>::LEN];
+    /// assert_eq!(array.len(), 3);
+    /// ```
+    pub const LEN: usize = K::INHABITANTS;
+
     /// Creates a map by providing a mapping function from `K` to `V`.
     ///
     /// Similar to [`array::from_fn`](std::array::from_fn).
@@ -47,6 +87,26 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
         }
     }
 
+    /// Creates a map where every key maps to a clone of `v`.
+    ///
+    /// Similar to [`vec!`] with a value and a length.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<Option<bool>, String>::repeat("x".to_string());
+    /// assert!(map.values().all(|v| v == "x"));
+    /// ```
+    pub fn repeat(v: V) -> Self
+    where
+        V: Clone,
+    {
+        Self {
+            array: vec![v; K::INHABITANTS].into_boxed_slice(),
+            _phantom: PhantomData,
+        }
+    }
+
     /// Tries to create a map by providing a mapping function from `K` to `Result<V, E>`.
     ///
     /// Returns the first error if any of the mappings fails.
@@ -57,6 +117,30 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
         })
     }
 
+    /// Tries to create a map by providing a mapping function from `K` to `Result<V, E>`,
+    /// like [`try_from_fn`](ExhaustiveMap::try_from_fn).
+    ///
+    /// If any of the mappings fails, this returns the first error along with the index,
+    /// as given by [`to_usize`](Finite::to_usize), of the key it failed for.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let res = ExhaustiveMap::<u8, u8>::try_from_fn_partial(|k| k.checked_mul(2).ok_or(k));
+    /// assert_eq!(res, Err((128, 128)));
+    /// ```
+    pub fn try_from_fn_partial<E>(
+        mut f: impl FnMut(K) -> Result<V, E>,
+    ) -> Result<Self, (usize, E)> {
+        Ok(Self {
+            array: K::iter_all()
+                .enumerate()
+                .map(|(i, k)| f(k).map_err(|e| (i, e)))
+                .collect::<Result<_, _>>()?,
+            _phantom: PhantomData,
+        })
+    }
+
     /// Creates a map by providing a mapping function from `usize` to `V`.
     /// The map is filled according to the [`Finite`] implementation of `K`.
     ///
@@ -82,11 +166,270 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
         }
     }
 
+    /// Creates a map where each key in the iterator maps to the paired value,
+    /// and every other key maps to the default value of `V`.
+    ///
+    /// If a key occurs multiple times, the last value is used.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_default_except([(true, 7)]);
+    /// assert_eq!(map[false], 0);
+    /// assert_eq!(map[true], 7);
+    /// ```
+    pub fn from_default_except(overrides: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        V: Default,
+    {
+        let mut map = Self::default();
+        map.overwrite(overrides);
+        map
+    }
+
+    /// Creates a map from a `HashMap`, where every key missing from the `HashMap` maps to the
+    /// default value of `V`.
+    ///
+    /// This is the lenient counterpart to the [`TryFrom<HashMap<K, V>>`](TryFrom) implementation,
+    /// which fails if any key is missing, and is the same as
+    /// [`from_default_except`](ExhaustiveMap::from_default_except).
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map =
+    ///     ExhaustiveMap::<Option<bool>, u8>::from_hashmap_or_default(HashMap::from([(None, 3)]));
+    /// assert_eq!(map.values().collect::<Vec<_>>(), [&3, &0, &0]);
+    /// ```
+    pub fn from_hashmap_or_default(map: HashMap<K, V>) -> Self
+    where
+        V: Default,
+    {
+        Self::from_default_except(map)
+    }
+
+    /// Creates a map from an iterator yielding exactly `K::INHABITANTS` values.
+    /// The map is filled according to the [`Finite`] implementation of `K`.
+    ///
+    /// Returns a [`LengthError`] if the iterator yields too few or too many values.
+    ///
+    /// ```
+    /// use exhaustive_map::{ExhaustiveMap, LengthError};
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_iter_exact([2, 3]).unwrap();
+    /// assert_eq!(map[false], 2);
+    /// assert_eq!(map[true], 3);
+    ///
+    /// let err = ExhaustiveMap::<bool, u8>::from_iter_exact([1, 2, 3]).unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     LengthError {
+    ///         expected: 2,
+    ///         actual: 3
+    ///     }
+    /// );
+    /// ```
+    pub fn from_iter_exact(iter: impl IntoIterator<Item = V>) -> Result<Self, LengthError> {
+        let array: Box<[V]> = iter.into_iter().collect();
+        if array.len() != K::INHABITANTS {
+            return Err(LengthError {
+                expected: K::INHABITANTS,
+                actual: array.len(),
+            });
+        }
+        Ok(Self {
+            array,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Creates a map from a boxed slice of values, ordered by the keys order provided by [`Finite`].
+    ///
+    /// Returns `Err` containing the input if its length is not `K::INHABITANTS`.
+    /// This is the same as the [`TryFrom<Box<[V]>>`](TryFrom) implementation.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_boxed_slice(Box::new([2, 3])).unwrap();
+    /// assert_eq!(map[true], 3);
+    ///
+    /// let err = ExhaustiveMap::<bool, u8>::from_boxed_slice(Box::new([1])).unwrap_err();
+    /// assert_eq!(*err, [1]);
+    /// ```
+    pub fn from_boxed_slice(values: Box<[V]>) -> Result<Self, Box<[V]>> {
+        values.try_into()
+    }
+
+    /// Converts the map into a boxed slice of the values, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This is the same as the [`From<ExhaustiveMap<K, V>>`](From) implementation for `Box<[V]>`,
+    /// and doesn't reallocate.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+    /// assert_eq!(*map.into_boxed_slice(), [10, 11]);
+    /// ```
+    pub fn into_boxed_slice(self) -> Box<[V]> {
+        self.into()
+    }
+
+    /// Creates a map from a `Vec` of values, ordered by the keys order provided by [`Finite`].
+    ///
+    /// Returns `Err` containing the input if its length is not `K::INHABITANTS`.
+    /// This is the same as the [`TryFrom<Vec<V>>`](TryFrom) implementation.
+    /// The `Vec` is reallocated if its capacity is larger than its length.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_vec(vec![2, 3]).unwrap();
+    /// assert_eq!(map[true], 3);
+    ///
+    /// let err = ExhaustiveMap::<bool, u8>::from_vec(vec![1, 2, 3]).unwrap_err();
+    /// assert_eq!(err, [1, 2, 3]);
+    /// ```
+    pub fn from_vec(values: Vec<V>) -> Result<Self, Vec<V>> {
+        values.try_into()
+    }
+
+    /// Converts the map into a `Vec` of the values, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This doesn't reallocate.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+    /// let mut values = map.into_vec();
+    /// values.push(12);
+    /// assert_eq!(values, [10, 11, 12]);
+    /// ```
+    pub fn into_vec(self) -> Vec<V> {
+        self.array.into_vec()
+    }
+
+    /// Returns the values as a slice, ordered by the keys order provided by [`Finite`].
+    ///
+    /// Indexing the map by a key `k` accesses the value at index
+    /// [`k.to_usize()`](Finite::to_usize), while indexing the slice by `i` accesses the value at
+    /// index `i` directly, regardless of the key type.
+    ///
+    /// The map can't implement `Index` for a positional newtype such as `ByIndex(usize)`, as it
+    /// would overlap with indexing by any key type `K` for which `ByIndex: Borrow<K>`,
+    /// so the slice is used for indexing by position instead.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<Option<u8>, u16>::from_fn(|k| match k {
+    ///     None => 1000,
+    ///     Some(v) => v as u16,
+    /// });
+    /// assert_eq!(map[Some(3)], 3);
+    /// assert_eq!(map.as_slice()[3], 2);
+    /// assert_eq!(map.as_slice()[0], 1000);
+    /// ```
+    pub fn as_slice(&self) -> &[V] {
+        &self.array
+    }
+
+    /// Returns the values as a mutable slice, ordered by the keys order provided by [`Finite`].
+    ///
+    /// See [`as_slice`](ExhaustiveMap::as_slice) for the difference between indexing the map and
+    /// indexing the slice.
+    pub fn as_mut_slice(&mut self) -> &mut [V] {
+        &mut self.array
+    }
+
+    /// Returns a raw pointer to the values.
+    ///
+    /// The pointer points to `K::INHABITANTS` contiguous values of type `V`,
+    /// ordered by the keys order provided by [`Finite`], so the value for the key `k` is at
+    /// offset [`k.to_usize()`](Finite::to_usize).
+    /// The pointer is valid for as long as the map isn't moved out of or dropped,
+    /// and must not be written through.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+    /// let ptr = map.as_ptr();
+    /// // SAFETY: The map has two values.
+    /// assert_eq!(unsafe { *ptr.add(1) }, 11);
+    /// ```
+    pub fn as_ptr(&self) -> *const V {
+        self.array.as_ptr()
+    }
+
+    /// Returns a raw mutable pointer to the values.
+    ///
+    /// See [`as_ptr`](ExhaustiveMap::as_ptr) for the layout.
+    pub fn as_mut_ptr(&mut self) -> *mut V {
+        self.array.as_mut_ptr()
+    }
+
+    /// Splits the values into the values for the keys before `k` and the values for the keys
+    /// from `k` onwards, relying on the keys order provided by [`Finite`].
+    ///
+    /// The first slice has length [`k.to_usize()`](Finite::to_usize).
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8);
+    /// assert_eq!(map.split_at_key(Some(false)), (&[0][..], &[1, 2][..]));
+    /// ```
+    pub fn split_at_key<Q: Borrow<K>>(&self, k: Q) -> (&[V], &[V]) {
+        self.array.split_at(k.borrow().to_usize())
+    }
+
+    /// Splits the values into mutable slices of the values for the keys before `k` and the values
+    /// for the keys from `k` onwards, relying on the keys order provided by [`Finite`].
+    ///
+    /// See [`split_at_key`](ExhaustiveMap::split_at_key).
+    pub fn split_at_key_mut<Q: Borrow<K>>(&mut self, k: Q) -> (&mut [V], &mut [V]) {
+        self.array.split_at_mut(k.borrow().to_usize())
+    }
+
+    /// Returns a reference to the value stored at `index`, i.e. the value of the key `k` with
+    /// [`k.to_usize()`](Finite::to_usize) equal to `index`.
+    ///
+    /// Returns an [`OutOfBounds`] error if `index` is not less than `K::INHABITANTS`.
+    ///
+    /// ```
+    /// use exhaustive_map::{ExhaustiveMap, OutOfBounds};
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+    /// assert_eq!(map.at(1), Ok(&11));
+    /// assert_eq!(map.at(2), Err(OutOfBounds { index: 2, len: 2 }));
+    /// ```
+    pub fn at(&self, index: usize) -> Result<&V, OutOfBounds> {
+        self.array.get(index).ok_or(OutOfBounds {
+            index,
+            len: K::INHABITANTS,
+        })
+    }
+
+    /// Returns a mutable reference to the value stored at `index`.
+    ///
+    /// See [`at`](ExhaustiveMap::at).
+    pub fn at_mut(&mut self, index: usize) -> Result<&mut V, OutOfBounds> {
+        self.array.get_mut(index).ok_or(OutOfBounds {
+            index,
+            len: K::INHABITANTS,
+        })
+    }
+
     /// Returns the number of elements in the map.
     ///
-    /// Always equal to `K::INHABITANTS`.
+    /// Always equal to `K::INHABITANTS` and [`LEN`](ExhaustiveMap::LEN).
     pub const fn len(&self) -> usize {
-        K::INHABITANTS
+        Self::LEN
     }
 
     /// Returns `true` if the map contains no elements.
@@ -108,6 +451,105 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
             .swap(k1.borrow().to_usize(), k2.borrow().to_usize())
     }
 
+    /// Returns mutable references to the values stored for `k1` and `k2`,
+    /// or `None` if `k1` and `k2` are the same key.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let mut map = ExhaustiveMap::<u8, u16>::from_fn(|k| k.into());
+    /// let (a, b) = map.get_disjoint_mut(7, 3).unwrap();
+    /// *a += *b;
+    /// assert_eq!(map[7], 10);
+    ///
+    /// assert!(map.get_disjoint_mut(7, 7).is_none());
+    /// ```
+    pub fn get_disjoint_mut<Q1: Borrow<K>, Q2: Borrow<K>>(
+        &mut self,
+        k1: Q1,
+        k2: Q2,
+    ) -> Option<(&mut V, &mut V)> {
+        let i = k1.borrow().to_usize();
+        let j = k2.borrow().to_usize();
+        if i < j {
+            let (a, b) = self.array.split_at_mut(j);
+            Some((&mut a[i], &mut b[0]))
+        } else if i > j {
+            let (a, b) = self.array.split_at_mut(i);
+            Some((&mut b[0], &mut a[j]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns mutable references to the values stored for each of the `keys`,
+    /// or `None` if any two of the keys are the same.
+    ///
+    /// This generalizes [`get_disjoint_mut`](ExhaustiveMap::get_disjoint_mut) to any number of keys.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let mut map = ExhaustiveMap::<u8, u16>::from_fn(|k| k.into());
+    /// let [a, b, c] = map.get_array_mut([1, 2, 3]).unwrap();
+    /// *a += *b + *c;
+    /// assert_eq!(map[1], 6);
+    ///
+    /// assert!(map.get_array_mut([1, 2, 1]).is_none());
+    /// ```
+    pub fn get_array_mut<const N: usize>(&mut self, keys: [K; N]) -> Option<[&mut V; N]> {
+        let indices = keys.map(|k| k.to_usize());
+        self.array.get_disjoint_mut(indices).ok()
+    }
+
+    /// Swaps all values with the values stored in `other`.
+    ///
+    /// This is `O(1)` as only the underlying storage is swapped.
+    pub fn swap_all(&mut self, other: &mut Self) {
+        std::mem::swap(&mut self.array, &mut other.array)
+    }
+
+    /// Moves the value stored for each key `k` to the key `perm[k]`.
+    ///
+    /// Returns a [`PermutationError`] and leaves the map unchanged
+    /// if `perm` is not a bijection, i.e. if some key is the target of multiple keys.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let mut map = ExhaustiveMap::<Option<bool>, char>::from_usize_fn(|i| ['a', 'b', 'c'][i]);
+    /// let perm = ExhaustiveMap::from_fn(|k: Option<bool>| match k {
+    ///     None => Some(true),
+    ///     Some(b) => b.then_some(false),
+    /// });
+    /// map.permute(&perm).unwrap();
+    /// assert_eq!(map.values().collect::<String>(), "bca");
+    /// ```
+    pub fn permute(&mut self, perm: &ExhaustiveMap<K, K>) -> Result<(), PermutationError> {
+        let mut seen = vec![false; K::INHABITANTS];
+        for target in perm.values() {
+            let j = target.to_usize();
+            if std::mem::replace(&mut seen[j], true) {
+                return Err(PermutationError { duplicate: j });
+            }
+        }
+
+        let mut permuted: Box<[Option<V>]> = (0..K::INHABITANTS).map(|_| None).collect();
+        for (v, target) in std::mem::take(&mut self.array)
+            .into_vec()
+            .into_iter()
+            .zip(perm.values())
+        {
+            permuted[target.to_usize()] = Some(v);
+        }
+        self.array = permuted
+            .into_vec()
+            .into_iter()
+            .map(Option::unwrap)
+            .collect();
+        Ok(())
+    }
+
     /// Replace the value stored for `k` with the default value of `V`, returning the previous stored value.
     pub fn take<Q: Borrow<K>>(&mut self, k: Q) -> V
     where
@@ -116,6 +558,32 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
         std::mem::take(&mut self[k])
     }
 
+    /// Replace the value stored for each key in the iterator with the paired value.
+    ///
+    /// Keys not present in the iterator keep their current value.
+    /// If a key occurs multiple times, the last value is kept.
+    pub fn overwrite(&mut self, iter: impl IntoIterator<Item = (K, V)>) {
+        for (k, v) in iter {
+            self[k] = v;
+        }
+    }
+
+    /// Replace the value stored for every entry satisfying the predicate with a clone of `default`.
+    ///
+    /// As entries can't be removed from the map, this is the analog of
+    /// [`HashMap::retain`](std::collections::HashMap::retain), except that it resets the entries
+    /// for which the predicate returns `true`.
+    pub fn reset_if(&mut self, mut pred: impl FnMut(&K, &V) -> bool, default: V)
+    where
+        V: Clone,
+    {
+        for (k, v) in self.iter_mut() {
+            if pred(&k, v) {
+                *v = default.clone();
+            }
+        }
+    }
+
     /// Change the values of the stored values via a mapping function.
     ///
     /// ```
@@ -134,6 +602,23 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
         }
     }
 
+    /// Change each of the stored values in place via a function.
+    ///
+    /// Unlike [`map_values`](ExhaustiveMap::map_values) this reuses the existing storage.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let mut map = ExhaustiveMap::from_fn(|k: bool| k as u8);
+    /// map.update_each(|v| *v += 10);
+    ///
+    /// assert_eq!(map[false], 10);
+    /// assert_eq!(map[true], 11);
+    /// ```
+    pub fn update_each(&mut self, f: impl FnMut(&mut V)) {
+        self.array.iter_mut().for_each(f);
+    }
+
     /// An iterator visiting all keys in the order provided by [`Finite`].
     ///
     /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
@@ -141,51 +626,440 @@ impl<K: Finite, V> ExhaustiveMap<K, V> {
         K::iter_all()
     }
 
+    /// Folds over all keys in the order provided by [`Finite`], without needing a map.
+    ///
+    /// This is the same as `K::iter_all().fold(init, f)`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let xor = ExhaustiveMap::<u8, ()>::fold_keys(0, |acc, k| acc ^ k);
+    /// assert_eq!(xor, 0);
+    ///
+    /// let xor = ExhaustiveMap::<bool, ()>::fold_keys(false, |acc, k| acc ^ k);
+    /// assert!(xor);
+    /// ```
+    pub fn fold_keys<B>(init: B, f: impl FnMut(B, K) -> B) -> B {
+        Self::keys().fold(init, f)
+    }
+
     /// An iterator visiting all values stored in the map, ordered by the keys order provided by [`Finite`].
-    pub fn values(&self) -> Values<V> {
+    pub fn values(&self) -> Values<'_, V> {
         Values(self.array.iter())
     }
 
     /// A mutable iterator visiting all values stored in the map, ordered by the keys order provided by [`Finite`].
-    pub fn values_mut(&mut self) -> ValuesMut<V> {
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
         ValuesMut(self.array.iter_mut())
     }
 
+    /// An iterator visiting all values stored in the map paired with their index,
+    /// ordered by the keys order provided by [`Finite`].
+    ///
+    /// The index of a value is the [`K::to_usize`](Finite::to_usize) of its key.
+    /// Unlike [`iter`](ExhaustiveMap::iter) this doesn't create any keys.
+    pub fn entries_indexed(&self) -> EntriesIndexed<'_, V> {
+        EntriesIndexed(self.array.iter().enumerate())
+    }
+
+    /// A mutable iterator visiting all values stored in the map paired with their index,
+    /// ordered by the keys order provided by [`Finite`].
+    ///
+    /// The index of a value is the [`K::to_usize`](Finite::to_usize) of its key.
+    /// Unlike [`iter_mut`](ExhaustiveMap::iter_mut) this doesn't create any keys.
+    pub fn enumerate_values_mut(&mut self) -> EnumerateValuesMut<'_, V> {
+        EnumerateValuesMut(self.array.iter_mut().enumerate())
+    }
+
     /// Creates a consuming iterator visiting all the values, ordered by the keys order provided by [`Finite`].
     /// The map cannot be used after calling this.
     pub fn into_values(self) -> IntoValues<V> {
         IntoValues(self.array.into_vec().into_iter())
     }
 
-    /// An iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    /// Converts the map into an array of the values, ordered by the keys order provided by [`Finite`].
     ///
-    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
-    pub fn iter(&self) -> Iter<K, V> {
-        Iter(Self::keys().zip(self.values()))
-    }
-
-    /// A mutable iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    /// Returns `Err` containing the input map if `N` is not equal to `K::INHABITANTS`.
     ///
-    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
-        IterMut(Self::keys().zip(self.values_mut()))
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+    /// assert_eq!(map.into_array(), Ok([10, 11]));
+    /// ```
+    pub fn into_array<const N: usize>(self) -> Result<[V; N], Self> {
+        match Box::<[V; N]>::try_from(self.array) {
+            Ok(array) => Ok(*array),
+            Err(array) => Err(Self {
+                array,
+                _phantom: PhantomData,
+            }),
+        }
     }
 
-    /// Creates a map with [`MaybeUninit`] values.
+    /// Returns a copy of the values as an array, ordered by the keys order provided by [`Finite`].
     ///
-    /// After every value have been initialized [`assume_init`](ExhaustiveMap::assume_init) can be
-    /// called to obtain a map with values of type `V`.
-    pub fn new_uninit() -> ExhaustiveMap<K, MaybeUninit<V>> {
-        ExhaustiveMap::from_usize_fn(|_| MaybeUninit::uninit())
+    /// Returns `None` if `N` is not equal to `K::INHABITANTS`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, String>::from_fn(|k| k.to_string());
+    /// assert_eq!(
+    ///     map.values_array(),
+    ///     Some(["false".to_string(), "true".to_string()])
+    /// );
+    /// assert_eq!(map.values_array::<3>(), None);
+    /// ```
+    pub fn values_array<const N: usize>(&self) -> Option<[V; N]>
+    where
+        V: Clone,
+    {
+        <&[V; N]>::try_from(&*self.array).ok().cloned()
     }
-}
 
-impl<K: Finite, V> ExhaustiveMap<K, Option<V>> {
-    /// Tries to convert an `ExhaustiveMap<K, Option<V>>` to an `ExhaustiveMap<K, V>`.
+    /// Converts the map into a map with keys of type `K2`, keeping the values in place.
     ///
-    /// If any of the values are `None`, this returns `Err` containing the input map.
-    pub fn try_unwrap_values(self) -> Result<ExhaustiveMap<K, V>, ExhaustiveMap<K, Option<V>>> {
-        if !self.array.iter().all(|v| v.is_some()) {
+    /// The value stored for the key `k` is moved to the key `k2` with
+    /// [`k2.to_usize()`](Finite::to_usize) equal to `k.to_usize()`,
+    /// so this is mainly useful for key types sharing the same encoding,
+    /// such as `[T; N]` and `(T, ..., T)` or [`BitArray<N>`](crate::BitArray) and `[bool; N]`.
+    ///
+    /// Returns `Err` containing the input map if `K2::INHABITANTS` is not equal to `K::INHABITANTS`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<[u8; 2], u16>::from_fn(|[a, b]| a as u16 * 256 + b as u16);
+    /// let map = map.cast_keys::<(u8, u8)>().unwrap();
+    /// assert_eq!(map[(1, 2)], 0x0102);
+    /// ```
+    pub fn cast_keys<K2: Finite>(self) -> Result<ExhaustiveMap<K2, V>, Self> {
+        if K2::INHABITANTS == K::INHABITANTS {
+            Ok(ExhaustiveMap {
+                array: self.array,
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Creates a map with keys of type `K2`, where the value stored for `k2` is a clone of the
+    /// value stored for `f(k2)`.
+    ///
+    /// This is intended for relabeling keys through a bijection `f` from `K2` to `K`.
+    /// Returns `Err` containing the input map if `K2::INHABITANTS` is not equal to `K::INHABITANTS`.
+    ///
+    /// ```
+    /// use exhaustive_map::{ExhaustiveMap, Finite};
+    ///
+    /// #[derive(Finite, Debug)]
+    /// enum Switch {
+    ///     On,
+    ///     Off,
+    /// }
+    ///
+    /// let map = ExhaustiveMap::<bool, &str>::from_fn(|b| if b { "on" } else { "off" });
+    /// let map = map.rekey(|s| matches!(s, Switch::On)).unwrap();
+    /// assert_eq!(map[Switch::On], "on");
+    /// assert_eq!(map[Switch::Off], "off");
+    /// ```
+    pub fn rekey<K2: Finite>(self, f: impl Fn(K2) -> K) -> Result<ExhaustiveMap<K2, V>, Self>
+    where
+        V: Clone,
+    {
+        if K2::INHABITANTS != K::INHABITANTS {
+            return Err(self);
+        }
+        Ok(ExhaustiveMap::from_fn(|k2| self[f(k2)].clone()))
+    }
+
+    /// An iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(Self::keys().zip(self.values()))
+    }
+
+    /// An iterator visiting each pair of consecutive entries stored in the map,
+    /// ordered by the keys order provided by [`Finite`].
+    ///
+    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) twice for each pair.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<Option<bool>, i32>::from_usize_fn(|i| (i * i) as i32);
+    /// let diffs: Vec<_> = map.pairs().map(|((_, a), (k, b))| (k, b - a)).collect();
+    /// assert_eq!(diffs, [(Some(false), 1), (Some(true), 3)]);
+    /// ```
+    pub fn pairs(&self) -> Pairs<'_, K, V> {
+        Pairs {
+            windows: self.array.windows(2).enumerate(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// An iterator visiting the entries that differ between `self` and `other`,
+    /// yielding the key, the value in `self` and the value in `other`,
+    /// ordered by the keys order provided by [`Finite`].
+    ///
+    /// This only creates keys for the entries that differ.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let old = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8);
+    /// let mut new = old.clone();
+    /// new[Some(true)] = 5;
+    /// let changes: Vec<_> = old.diff(&new).collect();
+    /// assert_eq!(changes, [(Some(true), &2, &5)]);
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Self) -> Diff<'a, K, V>
+    where
+        V: PartialEq,
+    {
+        Diff {
+            values: self.array.iter().zip(other.array.iter()).enumerate(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Converts the map into a [`KeyedMap`] which stores all the keys alongside the values,
+    /// so iterating doesn't call [`K::from_usize`](Finite::from_usize) for each key.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8).with_keys();
+    /// let entries: Vec<_> = map.iter().collect();
+    /// assert_eq!(
+    ///     entries,
+    ///     [(&None, &0), (&Some(false), &1), (&Some(true), &2)]
+    /// );
+    /// ```
+    pub fn with_keys(self) -> KeyedMap<K, V> {
+        KeyedMap {
+            keys: K::iter_all().collect(),
+            map: self,
+        }
+    }
+
+    /// A mutable iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut(Self::keys().zip(self.values_mut()))
+    }
+
+    /// Returns the number of values satisfying the predicate.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<u8, u8>::from_fn(|k| k);
+    /// assert_eq!(map.count(|v| v % 2 == 0), 128);
+    /// ```
+    pub fn count(&self, mut pred: impl FnMut(&V) -> bool) -> usize {
+        self.values().filter(|v| pred(v)).count()
+    }
+
+    /// Returns the sum of all values.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<u8, u32>::from_fn(|k| k.into());
+    /// assert_eq!(map.sum::<u32>(), 32640);
+    /// ```
+    pub fn sum<'a, S: Sum<&'a V>>(&'a self) -> S {
+        self.values().sum()
+    }
+
+    /// Returns the product of all values.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<[bool; 2], u32>::from_usize_fn(|i| i as u32 + 1);
+    /// assert_eq!(map.product::<u32>(), 24);
+    /// ```
+    pub fn product<'a, P: Product<&'a V>>(&'a self) -> P {
+        self.values().product()
+    }
+
+    /// Folds every entry into an accumulator, ordered by the keys order provided by [`Finite`].
+    ///
+    /// This is the same as `self.iter().fold(init, |acc, (k, v)| f(acc, k, v))`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<Option<bool>, u32>::from_fn(|k| match k {
+    ///     None => 1,
+    ///     Some(false) => 2,
+    ///     Some(true) => 3,
+    /// });
+    /// let weighted = map.reduce(0, |acc, k, v| acc + v * if k.is_some() { 10 } else { 1 });
+    /// assert_eq!(weighted, 51);
+    /// ```
+    pub fn reduce<B>(&self, init: B, mut f: impl FnMut(B, K, &V) -> B) -> B {
+        self.iter().fold(init, |acc, (k, v)| f(acc, k, v))
+    }
+
+    /// Returns the entry with the maximum value.
+    ///
+    /// If several entries have the maximum value, the first one in the order provided by
+    /// [`Finite`] is returned. Returns `None` only if the map is empty.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<u8, u8>::from_fn(|k| k % 100);
+    /// assert_eq!(map.max_by_value(), Some((99, &99)));
+    /// ```
+    pub fn max_by_value(&self) -> Option<(K, &V)>
+    where
+        V: Ord,
+    {
+        let i = self.argmax()?;
+        Some((nth_inhabitant(i), &self.array[i]))
+    }
+
+    /// Returns the entry with the minimum value.
+    ///
+    /// If several entries have the minimum value, the first one in the order provided by
+    /// [`Finite`] is returned. Returns `None` only if the map is empty.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<u8, u8>::from_fn(|k| k.abs_diff(50));
+    /// assert_eq!(map.min_by_value(), Some((50, &0)));
+    /// ```
+    pub fn min_by_value(&self) -> Option<(K, &V)>
+    where
+        V: Ord,
+    {
+        let i = self.argmin()?;
+        Some((nth_inhabitant(i), &self.array[i]))
+    }
+
+    /// Returns the index of the maximum value.
+    ///
+    /// This is the same as the [`K::to_usize`](Finite::to_usize) of the key returned by
+    /// [`max_by_value`](ExhaustiveMap::max_by_value), but doesn't create any keys.
+    /// If several entries have the maximum value, the lowest index is returned.
+    /// Returns `None` only if the map is empty.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, u8>::from_fn(|k| if k { 5 } else { 3 });
+    /// assert_eq!(map.argmax(), Some(1));
+    /// ```
+    pub fn argmax(&self) -> Option<usize>
+    where
+        V: Ord,
+    {
+        self.array
+            .iter()
+            .enumerate()
+            .reduce(|max, entry| if entry.1 > max.1 { entry } else { max })
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the index of the minimum value.
+    ///
+    /// This is the same as the [`K::to_usize`](Finite::to_usize) of the key returned by
+    /// [`min_by_value`](ExhaustiveMap::min_by_value), but doesn't create any keys.
+    /// If several entries have the minimum value, the lowest index is returned.
+    /// Returns `None` only if the map is empty.
+    pub fn argmin(&self) -> Option<usize>
+    where
+        V: Ord,
+    {
+        self.array
+            .iter()
+            .enumerate()
+            .reduce(|min, entry| if entry.1 < min.1 { entry } else { min })
+            .map(|(i, _)| i)
+    }
+
+    /// Creates a map with [`MaybeUninit`] values.
+    ///
+    /// After every value have been initialized [`assume_init`](ExhaustiveMap::assume_init) can be
+    /// called to obtain a map with values of type `V`.
+    pub fn new_uninit() -> ExhaustiveMap<K, MaybeUninit<V>> {
+        ExhaustiveMap::from_usize_fn(|_| MaybeUninit::uninit())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K: Finite, V: Send> ExhaustiveMap<K, V> {
+    /// A parallel mutable iterator visiting all values stored in the map.
+    ///
+    /// Requires the `rayon` feature.
+    pub fn par_values_mut(&mut self) -> rayon::slice::IterMut<'_, V> {
+        use rayon::iter::IntoParallelRefMutIterator;
+
+        self.array.par_iter_mut()
+    }
+
+    /// A parallel mutable iterator visiting all entries stored in the map.
+    ///
+    /// This creates new keys by calling [`K::from_usize`](Finite::from_usize) for each key.
+    ///
+    /// Requires the `rayon` feature.
+    pub fn par_iter_mut(
+        &mut self,
+    ) -> impl rayon::iter::IndexedParallelIterator<Item = (K, &mut V)> + '_
+    where
+        K: Send,
+    {
+        use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+
+        self.array.par_iter_mut().enumerate().map(|(i, v)| {
+            let k = nth_inhabitant(i);
+            (k, v)
+        })
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<K: Finite, V> ExhaustiveMap<K, V> {
+    /// Randomly permutes the values, so each value is assigned to a key chosen uniformly at random.
+    ///
+    /// Requires the `rand` feature.
+    pub fn shuffle(&mut self, rng: &mut impl rand::Rng) {
+        use rand::seq::SliceRandom;
+
+        self.array.shuffle(rng);
+    }
+}
+
+#[cfg(feature = "siphasher")]
+impl<K: Finite, V: Hash> ExhaustiveMap<K, V> {
+    /// Hashes the map using a hasher with a fixed key,
+    /// so the result is the same across runs, unlike with [`RandomState`](std::hash::RandomState).
+    ///
+    /// Requires the `siphasher` feature.
+    pub fn stable_hash(&self) -> u64 {
+        use std::hash::Hasher;
+
+        let mut hasher = siphasher::sip::SipHasher13::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<K: Finite, V> ExhaustiveMap<K, Option<V>> {
+    /// Tries to convert an `ExhaustiveMap<K, Option<V>>` to an `ExhaustiveMap<K, V>`.
+    ///
+    /// If any of the values are `None`, this returns `Err` containing the input map.
+    pub fn try_unwrap_values(self) -> Result<ExhaustiveMap<K, V>, ExhaustiveMap<K, Option<V>>> {
+        if !self.array.iter().all(|v| v.is_some()) {
             return Err(self);
         }
         let values: Box<[V]> = self
@@ -197,6 +1071,158 @@ impl<K: Finite, V> ExhaustiveMap<K, Option<V>> {
         // SAFETY: `values` has the correct length as we used `map`.
         Ok(unsafe { values.try_into().unwrap_unchecked() })
     }
+
+    /// Combines two maps of optional values.
+    ///
+    /// For each key, if both maps store `Some`, the values are combined using `f`.
+    /// If only one of the maps stores `Some`, that value is kept. Otherwise the result is `None`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let a = ExhaustiveMap::<Option<bool>, Option<u8>>::from_usize_fn(|i| (i > 0).then_some(1));
+    /// let b = ExhaustiveMap::<Option<bool>, Option<u8>>::from_usize_fn(|i| (i == 2).then_some(2));
+    /// let merged = a.merge_with(b, |x, y| x + y);
+    /// assert_eq!(
+    ///     merged.values().collect::<Vec<_>>(),
+    ///     [&None, &Some(1), &Some(3)]
+    /// );
+    /// ```
+    pub fn merge_with(self, other: Self, mut f: impl FnMut(V, V) -> V) -> Self {
+        let array = self
+            .array
+            .into_vec()
+            .into_iter()
+            .zip(other.array.into_vec())
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => Some(f(a, b)),
+                (a, b) => a.or(b),
+            })
+            .collect();
+        Self {
+            array,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K1: Finite, K2: Finite, V> ExhaustiveMap<K1, ExhaustiveMap<K2, V>> {
+    /// Converts a nested map into a map keyed by pairs of keys.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let nested = ExhaustiveMap::<bool, ExhaustiveMap<u8, u16>>::from_fn(|k1| {
+    ///     ExhaustiveMap::from_fn(|k2| k1 as u16 * 1000 + k2 as u16)
+    /// });
+    /// let flat = nested.clone().flatten();
+    /// assert_eq!(flat[(true, 7)], nested[true][7]);
+    /// assert_eq!(flat[(true, 7)], 1007);
+    /// ```
+    pub fn flatten(self) -> ExhaustiveMap<(K1, K2), V> {
+        // The index of `(k1, k2)` is `k1.to_usize() + k2.to_usize() * K1::INHABITANTS`,
+        // so we need to take the values from the inner maps in an interleaved order.
+        let mut rows: Vec<_> = self.into_values().map(|m| m.into_values()).collect();
+        ExhaustiveMap::from_usize_fn(|i| rows[i % K1::INHABITANTS].next().unwrap())
+    }
+}
+
+impl<V> ExhaustiveMap<(), V> {
+    /// Creates a map with the single key `()` mapping to `v`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::single("x");
+    /// assert_eq!(map[()], "x");
+    /// assert_eq!(map.into_single(), "x");
+    /// ```
+    pub fn single(v: V) -> Self {
+        Self {
+            array: Box::new([v]),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the value for the single key `()`.
+    pub fn into_single(self) -> V {
+        self.into_vec()
+            .pop()
+            .expect("a map with `()` keys has exactly one value")
+    }
+}
+
+impl<V, const N: usize> ExhaustiveMap<BitArray<N>, V> {
+    /// Converts the map into a map keyed by `[bool; N]`, which uses the same encoding as
+    /// [`BitArray<N>`], keeping the values in place.
+    ///
+    /// This is an infallible version of [`cast_keys`](ExhaustiveMap::cast_keys).
+    ///
+    /// ```
+    /// use exhaustive_map::{BitArray, ExhaustiveMap};
+    ///
+    /// let map = ExhaustiveMap::<BitArray<2>, u8>::from_fn(|bits| bits.0[1] as u8);
+    /// let map = map.into_array_map();
+    /// assert_eq!(map[[false, true]], 1);
+    /// ```
+    pub fn into_array_map(self) -> ExhaustiveMap<[bool; N], V> {
+        ExhaustiveMap {
+            array: self.array,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K1: Finite, K2: Finite, V> ExhaustiveMap<(K1, K2), V> {
+    /// An iterator visiting the entries for the keys `(k1, k2)` for a fixed `k1`,
+    /// i.e. the "row" of `k1`, ordered by the order of `K2` provided by [`Finite`].
+    ///
+    /// This is the inverse of [`flatten`](ExhaustiveMap::flatten) for a single row
+    /// and doesn't create any keys of type `K1`.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<(bool, Option<bool>), u8>::from_usize_fn(|i| i as u8);
+    /// let row: Vec<_> = map.project(true).collect();
+    /// assert_eq!(row, [(None, &1), (Some(false), &3), (Some(true), &5)]);
+    /// ```
+    pub fn project<Q: Borrow<K1>>(&self, k1: Q) -> Project<'_, K2, V> {
+        // The index of `(k1, k2)` is `k1.to_usize() + k2.to_usize() * K1::INHABITANTS`.
+        // If `K2` is uninhabited the array is empty, so the row is empty as well.
+        let values = self
+            .array
+            .get(k1.borrow().to_usize()..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(K1::INHABITANTS);
+        Project(K2::iter_all().zip(values))
+    }
+}
+
+impl<K: Finite, V, E> ExhaustiveMap<K, Result<V, E>> {
+    /// Converts an `ExhaustiveMap<K, Result<V, E>>` to a `Result<ExhaustiveMap<K, V>, E>`.
+    ///
+    /// If any of the values are `Err`, the first error in the order provided by [`Finite`]
+    /// is returned and the rest of the map is dropped.
+    /// Use [`try_unwrap_values`](ExhaustiveMap::try_unwrap_values) on
+    /// [`map_values(Result::ok)`](ExhaustiveMap::map_values) to keep the `Ok` values instead.
+    ///
+    /// ```
+    /// use exhaustive_map::ExhaustiveMap;
+    ///
+    /// let map = ExhaustiveMap::<bool, Result<u8, String>>::from_fn(|k| Ok(k as u8));
+    /// assert_eq!(map.transpose().unwrap()[true], 1);
+    ///
+    /// let map = ExhaustiveMap::<bool, Result<u8, String>>::from_fn(|k| Err(k.to_string()));
+    /// assert_eq!(map.transpose().unwrap_err(), "false");
+    /// ```
+    pub fn transpose(self) -> Result<ExhaustiveMap<K, V>, E> {
+        Ok(ExhaustiveMap {
+            array: self.into_values().collect::<Result<_, E>>()?,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 impl<K: Finite, V> ExhaustiveMap<K, MaybeUninit<V>> {
@@ -211,6 +1237,106 @@ impl<K: Finite, V> ExhaustiveMap<K, MaybeUninit<V>> {
     }
 }
 
+/// Creates a table of the [`Ordering`](std::cmp::Ordering) between every pair of values of `T`.
+///
+/// The value stored for `(a, b)` is `a.cmp(&b)`.
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use exhaustive_map::cmp_table;
+///
+/// let table = cmp_table::<Option<bool>>();
+/// assert_eq!(table[(None, Some(false))], Ordering::Less);
+/// assert_eq!(table[(Some(true), Some(false))], Ordering::Greater);
+/// ```
+pub fn cmp_table<T: Finite + Ord>() -> ExhaustiveMap<(T, T), std::cmp::Ordering> {
+    ExhaustiveMap::from_fn(|(a, b): (T, T)| a.cmp(&b))
+}
+
+/// Creates a table with a value for every pair of values of `A` and `B`,
+/// where the value stored for `(a, b)` is `f(a, b)`.
+///
+/// This is the same as [`ExhaustiveMap::from_fn`] with tuple keys,
+/// so `f` is called in the order of the keys of type `(A, B)` provided by [`Finite`],
+/// where `a` changes fastest.
+///
+/// ```
+/// use exhaustive_map::cartesian_product;
+///
+/// let mut calls = vec![];
+/// let table = cartesian_product(|a: bool, b: Option<bool>| {
+///     calls.push((a, b));
+///     a as u8 + b.is_some() as u8
+/// });
+/// assert_eq!(table[(true, Some(false))], 2);
+/// assert_eq!(
+///     calls[..3],
+///     [(false, None), (true, None), (false, Some(false))]
+/// );
+/// ```
+pub fn cartesian_product<A: Finite, B: Finite, V>(
+    mut f: impl FnMut(A, B) -> V,
+) -> ExhaustiveMap<(A, B), V> {
+    ExhaustiveMap::from_fn(|(a, b)| f(a, b))
+}
+
+/// Creates an [`ExhaustiveMap`] from a list of `pattern => value` arms.
+///
+/// The arms are used in a `match` on each key, so the compiler checks that every key is covered.
+/// Each value expression is evaluated once for each key matching its pattern.
+///
+/// ```
+/// use exhaustive_map::{exhaustive_map, Finite};
+///
+/// #[derive(Finite, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// let map = exhaustive_map! {
+///     Color::Red => 1,
+///     Color::Green => 2,
+///     Color::Blue => 3,
+/// };
+/// assert_eq!(map[Color::Green], 2);
+///
+/// let map = exhaustive_map! {
+///     None => 0,
+///     Some(false) | Some(true) => 1,
+/// };
+/// assert_eq!(map[Some(true)], 1);
+/// ```
+///
+/// A missing key fails to compile:
+/// ```compile_fail
+/// use exhaustive_map::{exhaustive_map, Finite};
+///
+/// #[derive(Finite, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// let map = exhaustive_map! {
+///     Color::Red => 1,
+///     Color::Green => 2,
+/// };
+/// ```
+// This is a declarative macro rather than a proc-macro in `exhaustive-map-macros`, as the
+// `match` already gives the compiler's exhaustiveness check and its error messages.
+#[macro_export]
+macro_rules! exhaustive_map {
+    ($($pat:pat => $value:expr),* $(,)?) => {
+        $crate::ExhaustiveMap::from_fn(|k| match k {
+            $($pat => $value,)*
+        })
+    };
+}
+
 impl<K: Finite, V> TryFrom<Box<[V]>> for ExhaustiveMap<K, V> {
     type Error = Box<[V]>;
 
@@ -259,6 +1385,39 @@ impl<const N: usize, K: Finite, V> TryFrom<[V; N]> for ExhaustiveMap<K, V> {
     }
 }
 
+/// Creates a map from an iterator yielding every key exactly once.
+///
+/// # Panics
+///
+/// Panics if any key is yielded more than once or not at all.
+///
+/// ```
+/// use exhaustive_map::ExhaustiveMap;
+///
+/// let map: ExhaustiveMap<bool, u8> = [(true, 3), (false, 2)].into_iter().collect();
+/// assert_eq!(map[false], 2);
+/// assert_eq!(map[true], 3);
+/// ```
+impl<K: Finite, V> FromIterator<(K, V)> for ExhaustiveMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = ExhaustiveMap::<K, Option<V>>::default();
+        for (k, v) in iter {
+            let i = k.to_usize();
+            assert!(
+                map.array[i].replace(v).is_none(),
+                "duplicate key with index {i} in iterator"
+            );
+        }
+        match map.try_unwrap_values() {
+            Ok(map) => map,
+            Err(map) => {
+                let i = map.array.iter().position(Option::is_none).unwrap();
+                panic!("missing key with index {i} in iterator")
+            }
+        }
+    }
+}
+
 impl<K: Finite + Eq + Hash, V> TryFrom<HashMap<K, V>> for ExhaustiveMap<K, V> {
     type Error = K;
 
@@ -279,88 +1438,519 @@ impl<K: Finite + Ord, V> From<ExhaustiveMap<K, V>> for BTreeMap<K, V> {
     }
 }
 
+/// An [`ExhaustiveMap`] together with all of its keys.
+///
+/// This `struct` is created by the [`ExhaustiveMap::with_keys`] method.
+/// It is useful when [`K::from_usize`](Finite::from_usize) is expensive and the map is iterated
+/// many times, as the keys are only created once.
+pub struct KeyedMap<K: Finite, V> {
+    keys: Box<[K]>,
+    map: ExhaustiveMap<K, V>,
+}
+
+impl<K: Finite, V> KeyedMap<K, V> {
+    /// Returns all the keys, ordered by the order provided by [`Finite`].
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns a reference to the underlying map.
+    pub fn map(&self) -> &ExhaustiveMap<K, V> {
+        &self.map
+    }
+
+    /// Returns a mutable reference to the underlying map.
+    pub fn map_mut(&mut self) -> &mut ExhaustiveMap<K, V> {
+        &mut self.map
+    }
+
+    /// Returns the underlying map, dropping the stored keys.
+    pub fn into_map(self) -> ExhaustiveMap<K, V> {
+        self.map
+    }
+
+    /// An iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    ///
+    /// Unlike [`ExhaustiveMap::iter`] this doesn't create any keys.
+    pub fn iter(&self) -> KeyedIter<'_, K, V> {
+        KeyedIter(self.keys.iter().zip(self.map.values()))
+    }
+
+    /// A mutable iterator visiting all entries stored in the map, ordered by the keys order provided by [`Finite`].
+    ///
+    /// Unlike [`ExhaustiveMap::iter_mut`] this doesn't create any keys.
+    pub fn iter_mut(&mut self) -> KeyedIterMut<'_, K, V> {
+        KeyedIterMut(self.keys.iter().zip(self.map.values_mut()))
+    }
+}
+
+impl<'a, K: Finite, V> IntoIterator for &'a KeyedMap<K, V> {
+    type Item = (&'a K, &'a V);
+
+    type IntoIter = KeyedIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Finite, V> IntoIterator for &'a mut KeyedMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    type IntoIter = KeyedIterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Finite + Debug, V: Debug> Debug for KeyedMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: Finite, V, Q: Borrow<K>> Index<Q> for KeyedMap<K, V> {
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        &self.map[index]
+    }
+}
+
+impl<K: Finite, V, Q: Borrow<K>> IndexMut<Q> for KeyedMap<K, V> {
+    fn index_mut(&mut self, index: Q) -> &mut Self::Output {
+        &mut self.map[index]
+    }
+}
+
+impl<K: Finite + Clone, V: Clone> Clone for KeyedMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            map: self.map.clone(),
+        }
+    }
+}
+
+/// The error returned by [`ExhaustiveMap::from_iter_exact`]
+/// when the iterator doesn't yield exactly `K::INHABITANTS` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthError {
+    /// The number of values required, i.e. `K::INHABITANTS`.
+    pub expected: usize,
+    /// The number of values actually yielded by the iterator.
+    pub actual: usize,
+}
+
+impl Display for LengthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected {} values, but got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for LengthError {}
+
+/// The error returned by [`ExhaustiveMap::at`] and [`ExhaustiveMap::at_mut`]
+/// when the index is out of bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    /// The index that was accessed.
+    pub index: usize,
+    /// The number of values in the map, i.e. `K::INHABITANTS`.
+    pub len: usize,
+}
+
+impl Display for OutOfBounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "index {} is out of bounds for a map with {} values",
+            self.index, self.len
+        )
+    }
+}
+
+impl Error for OutOfBounds {}
+
+/// The error returned by [`ExhaustiveMap::permute`] when the permutation is not a bijection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermutationError {
+    /// The index of a key which is the target of multiple keys.
+    pub duplicate: usize,
+}
+
+impl Display for PermutationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the key with index {} is the target of multiple keys",
+            self.duplicate
+        )
+    }
+}
+
+impl Error for PermutationError {}
+
 /// An iterator over the values of an [`ExhaustiveMap`].
 ///
-/// This `struct` is created by the [`ExhaustiveMap::values`] method.
-pub struct Values<'a, V>(std::slice::Iter<'a, V>);
+/// This `struct` is created by the [`ExhaustiveMap::values`] and [`ExhaustiveArrayMap::values`](crate::ExhaustiveArrayMap::values) methods.
+pub struct Values<'a, V>(pub(crate) std::slice::Iter<'a, V>);
 
 impl<'a, V> Iterator for Values<'a, V> {
     type Item = &'a V;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Values<'a, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Values<'a, V> {}
+
+/// A mutable iterator over the values of an [`ExhaustiveMap`].
+///
+/// This `struct` is created by the [`ExhaustiveMap::values_mut`] and [`ExhaustiveArrayMap::values_mut`](crate::ExhaustiveArrayMap::values_mut) methods.
+pub struct ValuesMut<'a, V>(pub(crate) std::slice::IterMut<'a, V>);
+
+impl<'a, V> Iterator for ValuesMut<'a, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for ValuesMut<'a, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for ValuesMut<'a, V> {}
+
+/// An owning iterator over the values of an [`ExhaustiveMap`].
+///
+/// This `struct` is created by the [`ExhaustiveMap::into_values`] method.
+pub struct IntoValues<V>(std::vec::IntoIter<V>);
+
+impl<V> Iterator for IntoValues<V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<V> DoubleEndedIterator for IntoValues<V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<V> ExactSizeIterator for IntoValues<V> {}
+
+impl<K: Finite, V: Default> Default for ExhaustiveMap<K, V> {
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+/// An iterator over the entries of an [`ExhaustiveMap`].
+///
+/// This `struct` is created by the [`ExhaustiveMap::iter`] and [`ExhaustiveArrayMap::iter`](crate::ExhaustiveArrayMap::iter) methods.
+pub struct Iter<'a, K: Finite, V>(pub(crate) std::iter::Zip<IterAll<K>, Values<'a, V>>);
+
+impl<'a, K: Finite, V> Iterator for Iter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Finite, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, K: Finite, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// A mutable iterator over the entries of an [`ExhaustiveMap`].
+///
+/// This `struct` is created by the [`ExhaustiveMap::iter_mut`] and [`ExhaustiveArrayMap::iter_mut`](crate::ExhaustiveArrayMap::iter_mut) methods.
+pub struct IterMut<'a, K: Finite, V>(pub(crate) std::iter::Zip<IterAll<K>, ValuesMut<'a, V>>);
+
+impl<'a, K: Finite, V> Iterator for IterMut<'a, K, V> {
+    type Item = (K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Finite, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, K: Finite, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+/// An owning iterator over the entries of an [`ExhaustiveMap`].
+///
+/// This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`ExhaustiveMap`]
+/// (provided by the [`IntoIterator`] trait).
+pub struct IntoIter<K: Finite, V>(std::iter::Zip<IterAll<K>, IntoValues<V>>);
+
+impl<K: Finite, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: Finite, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<K: Finite, V> ExactSizeIterator for IntoIter<K, V> {}
+
+/// An iterator over the values of an [`ExhaustiveMap`] paired with their index.
+///
+/// This `struct` is created by the [`ExhaustiveMap::entries_indexed`] method.
+pub struct EntriesIndexed<'a, V>(std::iter::Enumerate<std::slice::Iter<'a, V>>);
+
+impl<'a, V> Iterator for EntriesIndexed<'a, V> {
+    type Item = (usize, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for EntriesIndexed<'a, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for EntriesIndexed<'a, V> {}
+
+/// A mutable iterator over the values of an [`ExhaustiveMap`] paired with their index.
+///
+/// This `struct` is created by the [`ExhaustiveMap::enumerate_values_mut`] method.
+pub struct EnumerateValuesMut<'a, V>(std::iter::Enumerate<std::slice::IterMut<'a, V>>);
+
+impl<'a, V> Iterator for EnumerateValuesMut<'a, V> {
+    type Item = (usize, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for EnumerateValuesMut<'a, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for EnumerateValuesMut<'a, V> {}
+
+/// An iterator over the entries of a [`KeyedMap`].
+///
+/// This `struct` is created by the [`KeyedMap::iter`] method.
+pub struct KeyedIter<'a, K, V>(std::iter::Zip<std::slice::Iter<'a, K>, Values<'a, V>>);
+
+impl<'a, K, V> Iterator for KeyedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for KeyedIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
     }
 }
 
-/// A mutable iterator over the values of an [`ExhaustiveMap`].
+impl<'a, K, V> ExactSizeIterator for KeyedIter<'a, K, V> {}
+
+/// A mutable iterator over the entries of a [`KeyedMap`].
 ///
-/// This `struct` is created by the [`ExhaustiveMap::values_mut`] method.
-pub struct ValuesMut<'a, V>(std::slice::IterMut<'a, V>);
+/// This `struct` is created by the [`KeyedMap::iter_mut`] method.
+pub struct KeyedIterMut<'a, K, V>(std::iter::Zip<std::slice::Iter<'a, K>, ValuesMut<'a, V>>);
 
-impl<'a, V> Iterator for ValuesMut<'a, V> {
-    type Item = &'a mut V;
+impl<'a, K, V> Iterator for KeyedIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
-/// An owning iterator over the values of an [`ExhaustiveMap`].
+impl<'a, K, V> DoubleEndedIterator for KeyedIterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for KeyedIterMut<'a, K, V> {}
+
+/// An iterator over the entries of a row of an [`ExhaustiveMap`] keyed by pairs.
 ///
-/// This `struct` is created by the [`ExhaustiveMap::into_values`] method.
-pub struct IntoValues<V>(std::vec::IntoIter<V>);
+/// This `struct` is created by the [`ExhaustiveMap::project`] method.
+pub struct Project<'a, K2: Finite, V>(
+    std::iter::Zip<IterAll<K2>, std::iter::StepBy<std::slice::Iter<'a, V>>>,
+);
 
-impl<V> Iterator for IntoValues<V> {
-    type Item = V;
+impl<'a, K2: Finite, V> Iterator for Project<'a, K2, V> {
+    type Item = (K2, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
-impl<K: Finite, V: Default> Default for ExhaustiveMap<K, V> {
-    fn default() -> Self {
-        Self::from_fn(|_| V::default())
+impl<'a, K2: Finite, V> DoubleEndedIterator for Project<'a, K2, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
     }
 }
 
-/// An iterator over the entries of an [`ExhaustiveMap`].
+impl<'a, K2: Finite, V> ExactSizeIterator for Project<'a, K2, V> {}
+
+/// An iterator over the pairs of consecutive entries of an [`ExhaustiveMap`].
 ///
-/// This `struct` is created by the [`ExhaustiveMap::iter`] method.
-pub struct Iter<'a, K: Finite, V>(std::iter::Zip<IterAll<K>, Values<'a, V>>);
+/// This `struct` is created by the [`ExhaustiveMap::pairs`] method.
+pub struct Pairs<'a, K: Finite, V> {
+    windows: std::iter::Enumerate<std::slice::Windows<'a, V>>,
+    _phantom: PhantomData<fn() -> K>,
+}
 
-impl<'a, K: Finite, V> Iterator for Iter<'a, K, V> {
-    type Item = (K, &'a V);
+impl<'a, K: Finite, V> Pairs<'a, K, V> {
+    fn with_keys((i, w): (usize, &'a [V])) -> ((K, &'a V), (K, &'a V)) {
+        ((nth_inhabitant(i), &w[0]), (nth_inhabitant(i + 1), &w[1]))
+    }
+}
+
+impl<'a, K: Finite, V> Iterator for Pairs<'a, K, V> {
+    type Item = ((K, &'a V), (K, &'a V));
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.windows.next().map(Self::with_keys)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
     }
 }
 
-/// A mutable iterator over the entries of an [`ExhaustiveMap`].
+impl<'a, K: Finite, V> DoubleEndedIterator for Pairs<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.windows.next_back().map(Self::with_keys)
+    }
+}
+
+impl<'a, K: Finite, V> ExactSizeIterator for Pairs<'a, K, V> {}
+
+/// An iterator over the entries that differ between two [`ExhaustiveMap`]s.
 ///
-/// This `struct` is created by the [`ExhaustiveMap::iter_mut`] method.
-pub struct IterMut<'a, K: Finite, V>(std::iter::Zip<IterAll<K>, ValuesMut<'a, V>>);
+/// This `struct` is created by the [`ExhaustiveMap::diff`] method.
+pub struct Diff<'a, K: Finite, V> {
+    values: std::iter::Enumerate<std::iter::Zip<std::slice::Iter<'a, V>, std::slice::Iter<'a, V>>>,
+    _phantom: PhantomData<fn() -> K>,
+}
 
-impl<'a, K: Finite, V> Iterator for IterMut<'a, K, V> {
-    type Item = (K, &'a mut V);
+impl<'a, K: Finite, V> Diff<'a, K, V> {
+    fn differs((_, (a, b)): &(usize, (&'a V, &'a V))) -> bool
+    where
+        V: PartialEq,
+    {
+        a != b
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+    fn with_key((i, (a, b)): (usize, (&'a V, &'a V))) -> (K, &'a V, &'a V) {
+        let k = nth_inhabitant(i);
+        (k, a, b)
     }
 }
 
-/// An owning iterator over the entries of an [`ExhaustiveMap`].
-///
-/// This `struct` is created by the [`into_iter`](IntoIterator::into_iter) method on [`ExhaustiveMap`]
-/// (provided by the [`IntoIterator`] trait).
-pub struct IntoIter<K: Finite, V>(std::iter::Zip<IterAll<K>, IntoValues<V>>);
-
-impl<K: Finite, V> Iterator for IntoIter<K, V> {
-    type Item = (K, V);
+impl<'a, K: Finite, V: PartialEq> Iterator for Diff<'a, K, V> {
+    type Item = (K, &'a V, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.values.find(Self::differs).map(Self::with_key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.values.size_hint().1)
+    }
+}
+
+impl<'a, K: Finite, V: PartialEq> DoubleEndedIterator for Diff<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.values.rfind(Self::differs).map(Self::with_key)
     }
 }
 
@@ -460,6 +2050,7 @@ unsafe impl<K: Finite, V> Sync for ExhaustiveMap<K, V> where Box<[V]>: Sync {}
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::InRange;
 
     #[derive(Finite)]
     struct Key(PhantomData<*mut u8>);
@@ -472,6 +2063,61 @@ mod test {
 
     const _: () = assert_implements_traits::<ExhaustiveMap<Key, bool>>();
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_values_mut() {
+        use rayon::iter::ParallelIterator;
+
+        let mut m = ExhaustiveMap::<u16, u64>::from_fn(|k| k as u64);
+        let mut expected = m.clone();
+        m.par_values_mut().for_each(|v| *v *= *v);
+        expected.values_mut().for_each(|v| *v *= *v);
+        assert_eq!(m, expected);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_mut() {
+        use rayon::iter::ParallelIterator;
+
+        let mut m = ExhaustiveMap::<u16, u64>::from_fn(|k| k as u64);
+        let mut expected = m.clone();
+        m.par_iter_mut().for_each(|(k, v)| *v += k as u64 * 3);
+        expected.iter_mut().for_each(|(k, v)| *v += k as u64 * 3);
+        assert_eq!(m, expected);
+        assert_eq!(m[100], 400);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_shuffle() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let original = ExhaustiveMap::<u8, u16>::from_fn(|k| k as u16 % 100);
+        let mut m = original.clone();
+        m.shuffle(&mut rng);
+        assert_ne!(m, original);
+
+        let mut values: Vec<_> = m.into_values().collect();
+        values.sort_unstable();
+        let mut expected: Vec<_> = original.into_values().collect();
+        expected.sort_unstable();
+        assert_eq!(values, expected);
+    }
+
+    #[cfg(feature = "siphasher")]
+    #[test]
+    fn test_stable_hash() {
+        let m = ExhaustiveMap::<u8, u16>::from_fn(|k| k as u16 * 3);
+        assert_eq!(m.stable_hash(), m.stable_hash());
+        assert_eq!(m.stable_hash(), m.clone().stable_hash());
+
+        let mut m2 = m.clone();
+        m2[7] += 1;
+        assert_ne!(m.stable_hash(), m2.stable_hash());
+    }
+
     #[test]
     fn test_uninit() {
         let mut m = ExhaustiveMap::<bool, u8>::new_uninit();
@@ -482,6 +2128,27 @@ mod test {
         println!("{m:?}");
     }
 
+    #[test]
+    fn test_entries_indexed() {
+        let m = ExhaustiveMap::<Option<bool>, char>::from_usize_fn(|i| (b'a' + i as u8) as char);
+        assert_eq!(
+            m.entries_indexed().collect::<Vec<_>>(),
+            [(0, &'a'), (1, &'b'), (2, &'c')]
+        );
+        assert_eq!(m.entries_indexed().next_back(), Some((2, &'c')));
+    }
+
+    #[test]
+    fn test_enumerate_values_mut() {
+        let mut m = ExhaustiveMap::<u8, usize>::default();
+        for (i, v) in m.enumerate_values_mut() {
+            *v = i * 2;
+        }
+        for (k, v) in &m {
+            assert_eq!(*v, k as usize * 2);
+        }
+    }
+
     #[test]
     fn test_conversion() {
         let m: ExhaustiveMap<bool, u8> = [2, 3].try_into().unwrap();
@@ -489,6 +2156,793 @@ mod test {
         assert_eq!(m[true], 3);
     }
 
+    #[test]
+    fn test_reverse_iteration() {
+        let mut m = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8);
+        let keys = [Some(true), Some(false), None];
+
+        assert_eq!(m.values().rev().collect::<Vec<_>>(), [&2, &1, &0]);
+        assert_eq!(m.values_mut().rev().collect::<Vec<_>>(), [&2, &1, &0]);
+        assert_eq!(
+            m.iter().rev().collect::<Vec<_>>(),
+            [(keys[0], &2), (keys[1], &1), (keys[2], &0)]
+        );
+        assert_eq!(
+            m.iter_mut().rev().collect::<Vec<_>>(),
+            [(keys[0], &mut 2), (keys[1], &mut 1), (keys[2], &mut 0)]
+        );
+        assert_eq!(
+            m.enumerate_values_mut().rev().collect::<Vec<_>>(),
+            [(2, &mut 2), (1, &mut 1), (0, &mut 0)]
+        );
+        assert_eq!(m.clone().into_values().rev().collect::<Vec<_>>(), [2, 1, 0]);
+        assert_eq!(
+            m.into_iter().rev().collect::<Vec<_>>(),
+            [(keys[0], 2), (keys[1], 1), (keys[2], 0)]
+        );
+    }
+
+    #[test]
+    fn test_iterator_len() {
+        let mut m = ExhaustiveMap::<u8, u8>::from_fn(|k| k);
+        assert_eq!(m.values().len(), u8::INHABITANTS);
+        assert_eq!(m.values_mut().len(), u8::INHABITANTS);
+        assert_eq!(m.enumerate_values_mut().len(), u8::INHABITANTS);
+        assert_eq!(m.iter().len(), u8::INHABITANTS);
+        assert_eq!(m.iter_mut().len(), u8::INHABITANTS);
+        assert_eq!(m.clone().into_values().len(), u8::INHABITANTS);
+        assert_eq!(ExhaustiveMap::<u8, u8>::keys().len(), u8::INHABITANTS);
+
+        let mut iter = m.into_iter();
+        assert_eq!(iter.len(), u8::INHABITANTS);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), u8::INHABITANTS - 2);
+        assert_eq!(
+            iter.size_hint(),
+            (u8::INHABITANTS - 2, Some(u8::INHABITANTS - 2))
+        );
+    }
+
+    #[test]
+    fn test_next_back() {
+        let m = ExhaustiveMap::<u8, u8>::from_fn(|k| k);
+        let mut iter = m.iter();
+        assert_eq!(iter.next_back(), Some((255, &255)));
+        assert_eq!(iter.next(), Some((0, &0)));
+        assert_eq!(iter.next_back(), Some((254, &254)));
+        assert_eq!(iter.count(), 253);
+    }
+
+    #[test]
+    fn test_cmp_table() {
+        use std::cmp::Ordering::*;
+
+        let table = cmp_table::<bool>();
+        assert_eq!(
+            table.iter().collect::<Vec<_>>(),
+            [
+                ((false, false), &Equal),
+                ((true, false), &Greater),
+                ((false, true), &Less),
+                ((true, true), &Equal),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_default_except() {
+        let m = ExhaustiveMap::<u8, u32>::from_default_except([(3, 30), (200, 2000)]);
+        for (k, &v) in &m {
+            match k {
+                3 => assert_eq!(v, 30),
+                200 => assert_eq!(v, 2000),
+                _ => assert_eq!(v, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_keys() {
+        use crate::BitArray;
+
+        let m = ExhaustiveMap::<[bool; 3], u8>::from_usize_fn(|i| i as u8);
+        let m = m.cast_keys::<BitArray<3>>().unwrap();
+        for (k, &v) in &m {
+            assert_eq!(k.to_usize(), v as usize);
+            assert_eq!(
+                m[k],
+                ExhaustiveMap::<[bool; 3], u8>::from_usize_fn(|i| i as u8)[k.0]
+            );
+        }
+
+        let m = m.cast_keys::<u8>().unwrap_err();
+        assert_eq!(m.len(), 8);
+    }
+
+    #[test]
+    fn test_rekey() {
+        #[derive(Finite, Debug, PartialEq)]
+        enum Side {
+            Left,
+            Right,
+        }
+
+        let m = ExhaustiveMap::<bool, u8>::from_fn(|b| b as u8 + 1);
+        let m2 = m.clone().rekey(|s| s == Side::Left).unwrap();
+        assert_eq!(m2[Side::Left], 2);
+        assert_eq!(m2[Side::Right], 1);
+
+        assert_eq!(m.clone().rekey(|_: Option<bool>| false).unwrap_err(), m);
+    }
+
+    #[test]
+    fn test_pairs() {
+        let m = ExhaustiveMap::<u8, u8>::from_fn(|k| k);
+        let mut pairs = m.pairs();
+        assert_eq!(pairs.len(), 255);
+        assert_eq!(pairs.next(), Some(((0, &0), (1, &1))));
+        assert_eq!(pairs.next_back(), Some(((254, &254), (255, &255))));
+
+        assert_eq!(ExhaustiveMap::<(), u8>::default().pairs().count(), 0);
+    }
+
+    #[test]
+    fn test_permute() {
+        let mut m = ExhaustiveMap::<u8, u16>::from_fn(|k| k.into());
+        let perm = ExhaustiveMap::<u8, u8>::from_fn(|k| k.wrapping_add(10));
+        m.permute(&perm).unwrap();
+        for (k, &v) in &m {
+            assert_eq!(v, k.wrapping_sub(10) as u16);
+        }
+    }
+
+    #[test]
+    fn test_permute_not_bijective() {
+        let mut m = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8);
+        let perm = ExhaustiveMap::from_fn(|k: Option<bool>| k.map(|_| true));
+        let err = m.permute(&perm).unwrap_err();
+        assert_eq!(err, PermutationError { duplicate: 2 });
+        assert_eq!(
+            err.to_string(),
+            "the key with index 2 is the target of multiple keys"
+        );
+        assert_eq!(m.values().collect::<Vec<_>>(), [&0, &1, &2]);
+    }
+
+    #[test]
+    fn test_update_each() {
+        let mut m = ExhaustiveMap::<u8, u32>::from_fn(|k| k.into());
+        let ptr = m.as_slice().as_ptr();
+        m.update_each(|v| *v += 1);
+        assert_eq!(m.as_slice().as_ptr(), ptr);
+        for (k, &v) in &m {
+            assert_eq!(v, k as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_split_at_key() {
+        let mut m = ExhaustiveMap::<u8, u8>::from_fn(|k| k);
+        let (low, high) = m.split_at_key(128);
+        assert_eq!(low, (0..128).collect::<Vec<_>>());
+        assert_eq!(high, (128..=255).collect::<Vec<_>>());
+
+        let (low, high) = m.split_at_key_mut(128);
+        low.fill(0);
+        high.fill(1);
+        assert_eq!(m[127], 0);
+        assert_eq!(m[128], 1);
+
+        let (low, high) = m.split_at_key(0);
+        assert_eq!((low.len(), high.len()), (0, 256));
+    }
+
+    #[test]
+    fn test_repeat() {
+        let m = ExhaustiveMap::<u8, [u8; 3]>::repeat([1, 2, 3]);
+        assert_eq!(m.len(), 256);
+        assert!(m.values().all(|v| *v == [1, 2, 3]));
+        assert!(ExhaustiveMap::<std::convert::Infallible, u8>::repeat(1).is_empty());
+    }
+
+    #[test]
+    fn test_merge_with() {
+        let a = ExhaustiveMap::<[bool; 2], Option<String>>::from_fn(|[x, _]| x.then(|| "a".into()));
+        let b = ExhaustiveMap::<[bool; 2], Option<String>>::from_fn(|[_, y]| y.then(|| "b".into()));
+        let m = a.merge_with(b, |a, b| a + &b);
+        assert_eq!(m[[false, false]], None);
+        assert_eq!(m[[true, false]].as_deref(), Some("a"));
+        assert_eq!(m[[false, true]].as_deref(), Some("b"));
+        assert_eq!(m[[true, true]].as_deref(), Some("ab"));
+    }
+
+    #[test]
+    fn test_len_const() {
+        const N: usize = ExhaustiveMap::<bool, u8>::LEN;
+        let array = [1u8; N];
+        assert_eq!(array.len(), 2);
+        assert_eq!(ExhaustiveMap::<bool, u8>::default().len(), N);
+        assert_eq!(ExhaustiveMap::<u8, ()>::LEN, 256);
+    }
+
+    #[test]
+    fn test_with_keys() {
+        let mut m = ExhaustiveMap::<u8, u16>::from_fn(|k| k.into()).with_keys();
+        assert_eq!(m.keys().len(), 256);
+        for (k, v) in &mut m {
+            *v += *k as u16;
+        }
+        assert_eq!(m[3], 6);
+        assert_eq!(m.iter().next_back(), Some((&255, &510)));
+        m.map_mut()[0] = 1;
+        assert_eq!(m.into_map().values().take(2).collect::<Vec<_>>(), [&1, &2]);
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut m = ExhaustiveMap::<[bool; 2], u8>::from_usize_fn(|i| i as u8);
+        m.overwrite([([true, false], 10), ([true, true], 30)]);
+        assert_eq!(m.values().collect::<Vec<_>>(), [&0, &10, &2, &30]);
+    }
+
+    #[test]
+    fn test_reset_if() {
+        let mut m = ExhaustiveMap::<u8, u16>::from_fn(|k| k as u16 + 1000);
+        m.reset_if(|k, _| k % 2 == 1, 0);
+        for (k, v) in m {
+            if k % 2 == 1 {
+                assert_eq!(v, 0);
+            } else {
+                assert_eq!(v, k as u16 + 1000);
+            }
+        }
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut m = ExhaustiveMap::<char, u32>::from_fn(|c| c as u32);
+        let after_gap = char::from_u32(0xE000).unwrap();
+        assert_eq!(m[after_gap], 0xE000);
+        assert_eq!(m.as_slice()[0xD800], 0xE000);
+        assert_eq!(m.as_slice()[after_gap.to_usize()], 0xE000);
+
+        m.as_mut_slice()[0xD800] = 0;
+        assert_eq!(m[after_gap], 0);
+    }
+
+    #[test]
+    fn test_as_ptr() {
+        let mut m = ExhaustiveMap::<Option<bool>, u16>::from_usize_fn(|i| i as u16 * 100);
+        let ptr = m.as_ptr();
+        for (k, v) in &m {
+            // SAFETY: `k.to_usize()` is less than the number of values.
+            assert_eq!(unsafe { *ptr.add(k.to_usize()) }, *v);
+        }
+
+        let ptr = m.as_mut_ptr();
+        // SAFETY: The map has three values, and no other references to them exist.
+        unsafe { *ptr.add(2) = 7 };
+        assert_eq!(m[Some(true)], 7);
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut m = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8);
+        let (a, b) = m.get_disjoint_mut(None, Some(true)).unwrap();
+        assert_eq!((*a, *b), (0, 2));
+        std::mem::swap(a, b);
+        let (a, b) = m.get_disjoint_mut(Some(true), Some(false)).unwrap();
+        assert_eq!((*a, *b), (0, 1));
+        assert_eq!(m.values().collect::<Vec<_>>(), [&2, &1, &0]);
+
+        assert_eq!(m.get_disjoint_mut(Some(false), Some(false)), None);
+    }
+
+    #[test]
+    fn test_get_array_mut() {
+        let mut m = ExhaustiveMap::<Option<bool>, u8>::from_usize_fn(|i| i as u8);
+        let [a, b, c] = m.get_array_mut([Some(true), None, Some(false)]).unwrap();
+        assert_eq!((*a, *b, *c), (2, 0, 1));
+        std::mem::swap(a, b);
+        *c = 10;
+        assert_eq!(m.values().collect::<Vec<_>>(), [&2, &10, &0]);
+
+        assert_eq!(m.get_array_mut([None, Some(true), None]), None);
+        assert_eq!(m.get_array_mut([Some(false); 3]), None);
+        assert_eq!(m.get_array_mut([]), Some([]));
+    }
+
+    #[test]
+    fn test_swap_all() {
+        let mut m1 = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8);
+        let mut m2 = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+        m1.swap_all(&mut m2);
+        assert_eq!(m1[false], 10);
+        assert_eq!(m1[true], 11);
+        assert_eq!(m2[false], 0);
+        assert_eq!(m2[true], 1);
+    }
+
+    #[test]
+    fn test_min_max_by_value() {
+        let m = ExhaustiveMap::<u8, u8>::from_fn(|k| k.abs_diff(100));
+        assert_eq!(m.min_by_value(), Some((100, &0)));
+        assert_eq!(m.max_by_value(), Some((255, &155)));
+
+        let m = ExhaustiveMap::<u8, u8>::from_fn(|k| k % 3);
+        assert_eq!(m.min_by_value(), Some((0, &0)));
+        assert_eq!(m.max_by_value(), Some((2, &2)));
+
+        let m = ExhaustiveMap::<std::convert::Infallible, u8>::default();
+        assert_eq!(m.min_by_value(), None);
+        assert_eq!(m.max_by_value(), None);
+    }
+
+    #[test]
+    fn test_argmin_argmax() {
+        let values = [3, 7, 1, 7, 0, 5, 0];
+        let m = ExhaustiveMap::<InRange<0, 7>, u8>::try_from(values).unwrap();
+
+        let mut max = 0;
+        let mut min = 0;
+        for (i, v) in values.iter().enumerate() {
+            if *v > values[max] {
+                max = i;
+            }
+            if *v < values[min] {
+                min = i;
+            }
+        }
+        assert_eq!(m.argmax(), Some(max));
+        assert_eq!(m.argmin(), Some(min));
+        assert_eq!(m.argmax(), Some(1));
+        assert_eq!(m.argmin(), Some(4));
+
+        let m = ExhaustiveMap::<std::convert::Infallible, u8>::default();
+        assert_eq!(m.argmax(), None);
+        assert_eq!(m.argmin(), None);
+    }
+
+    #[test]
+    fn test_from_iter_exact() {
+        let m = ExhaustiveMap::<u8, u16>::from_iter_exact(100..356).unwrap();
+        assert_eq!(m[0], 100);
+        assert_eq!(m[255], 355);
+    }
+
+    #[test]
+    fn test_from_iter_exact_too_short() {
+        let err = ExhaustiveMap::<u8, u16>::from_iter_exact(0..255).unwrap_err();
+        assert_eq!(
+            err,
+            LengthError {
+                expected: 256,
+                actual: 255
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_iter_exact_too_long() {
+        let err = ExhaustiveMap::<u8, u16>::from_iter_exact(0..257).unwrap_err();
+        assert_eq!(
+            err,
+            LengthError {
+                expected: 256,
+                actual: 257
+            }
+        );
+    }
+
+    #[test]
+    fn test_at() {
+        let mut m = ExhaustiveMap::<u8, u16>::from_fn(|k| k.into());
+        assert_eq!(m.at(0), Ok(&0));
+        assert_eq!(m.at(255), Ok(&255));
+        *m.at_mut(3).unwrap() = 1000;
+        assert_eq!(m[3], 1000);
+
+        let err = OutOfBounds {
+            index: 256,
+            len: 256,
+        };
+        assert_eq!(m.at(256), Err(err));
+        assert_eq!(m.at_mut(256), Err(err));
+        assert_eq!(
+            err.to_string(),
+            "index 256 is out of bounds for a map with 256 values"
+        );
+        assert_eq!(
+            ExhaustiveMap::<std::convert::Infallible, u8>::default().at(0),
+            Err(OutOfBounds { index: 0, len: 0 })
+        );
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let m: ExhaustiveMap<u8, u16> = (0..=255u8).rev().map(|k| (k, k as u16 * 3)).collect();
+        for (k, v) in m {
+            assert_eq!(v, k as u16 * 3);
+        }
+    }
+
+    #[test]
+    #[should_panic = "missing key with index 1 in iterator"]
+    fn test_from_iter_missing_key() {
+        let _: ExhaustiveMap<Option<bool>, u8> = [(None, 0), (Some(true), 2)].into_iter().collect();
+    }
+
+    #[test]
+    #[should_panic = "duplicate key with index 0 in iterator"]
+    fn test_from_iter_duplicate_key() {
+        let _: ExhaustiveMap<bool, u8> = [(false, 0), (true, 1), (false, 2)].into_iter().collect();
+    }
+
+    #[test]
+    fn test_into_array() {
+        let m = ExhaustiveMap::<bool, u8>::from_fn(|k| k as u8 + 10);
+        let m = m.into_array::<3>().unwrap_err();
+        let m = m.into_array::<1>().unwrap_err();
+        assert_eq!(m.into_array::<2>(), Ok([10, 11]));
+    }
+
+    #[test]
+    fn test_into_array_map() {
+        let m = ExhaustiveMap::<BitArray<9>, u16>::from_fn(|bits| bits.to_usize() as u16 * 7);
+        let expected = m.values().copied().collect::<Vec<_>>();
+        let m = m.into_array_map();
+        assert_eq!(m.values().copied().collect::<Vec<_>>(), expected);
+        for (k, v) in m {
+            assert_eq!(v, BitArray(k).to_usize() as u16 * 7);
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = ExhaustiveMap::<u8, u16>::from_fn(|k| k.into());
+        let mut b = a.clone();
+        assert_eq!(a.diff(&b).next(), None);
+
+        b[3] = 1000;
+        b[200] = 2000;
+        assert_eq!(
+            a.diff(&b).collect::<Vec<_>>(),
+            [(3, &3, &1000), (200, &200, &2000)]
+        );
+        assert_eq!(b.diff(&a).next_back(), Some((200, &2000, &200)));
+    }
+
+    #[test]
+    fn test_vec_round_trip() {
+        let m = ExhaustiveMap::<Option<u8>, u16>::from_usize_fn(|i| i as u16);
+        let mut values = m.clone().into_vec();
+        assert_eq!(values.len(), 257);
+        assert_eq!(values[1], 1);
+
+        values.pop();
+        let mut values = ExhaustiveMap::<Option<u8>, u16>::from_vec(values).unwrap_err();
+        assert_eq!(values.len(), 256);
+
+        values.push(256);
+        assert_eq!(ExhaustiveMap::from_vec(values), Ok(m));
+    }
+
+    #[test]
+    fn test_truth_table() {
+        let and = ExhaustiveMap::<[bool; 2], bool>::from_fn(|[a, b]| a && b);
+        assert_eq!(
+            and.iter().collect::<Vec<_>>(),
+            [
+                ([false, false], &false),
+                ([true, false], &false),
+                ([false, true], &false),
+                ([true, true], &true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_from_fn_partial() {
+        let mut calls = 0;
+        let res = ExhaustiveMap::<Option<bool>, u8>::try_from_fn_partial(|k| {
+            calls += 1;
+            match k {
+                Some(true) => Err("failed"),
+                _ => Ok(1),
+            }
+        });
+        assert_eq!(res, Err((2, "failed")));
+        assert_eq!(calls, 3);
+
+        let res = ExhaustiveMap::<bool, u8>::try_from_fn_partial(|k| Ok::<_, ()>(k as u8));
+        assert_eq!(res, Ok(ExhaustiveMap::from_fn(|k| k as u8)));
+    }
+
+    #[test]
+    fn test_exhaustive_map_macro() {
+        #[derive(Finite)]
+        enum Key {
+            A,
+            B(bool),
+            C { x: bool, y: Option<bool> },
+        }
+
+        let m = exhaustive_map! {
+            Key::A => 'a',
+            Key::B(false) => 'b',
+            Key::B(true) => 'B',
+            Key::C { x: _, y: None } => 'c',
+            Key::C { x, y: Some(_) } => if x { 'X' } else { 'x' },
+        };
+        assert_eq!(m.values().collect::<String>(), "abBccxXxX");
+
+        let k = 5;
+        let m: ExhaustiveMap<bool, u8> = exhaustive_map! {
+            false => k,
+            true => k + 1,
+        };
+        assert_eq!(m.into_array(), Ok([5, 6]));
+    }
+
+    #[test]
+    fn test_values_array() {
+        let m = ExhaustiveMap::<bool, Vec<u8>>::from_fn(|k| vec![k as u8; 2]);
+        assert_eq!(m.values_array(), Some([vec![0, 0], vec![1, 1]]));
+        assert_eq!(m.values_array::<1>(), None);
+        assert_eq!(m.values_array::<0>(), None);
+        assert_eq!(
+            ExhaustiveMap::<std::convert::Infallible, u8>::default().values_array(),
+            Some([])
+        );
+    }
+
+    #[test]
+    fn test_fold_keys() {
+        let sum = ExhaustiveMap::<u8, ()>::fold_keys(0u32, |acc, k| acc + k as u32);
+        assert_eq!(sum, 255 * 256 / 2);
+        let keys = ExhaustiveMap::<Option<bool>, u8>::fold_keys(vec![], |mut acc, k| {
+            acc.push(k);
+            acc
+        });
+        assert_eq!(keys, [None, Some(false), Some(true)]);
+    }
+
+    #[test]
+    fn test_from_hashmap_or_default() {
+        let partial = HashMap::from([(3u8, 30u16), (200, 2000)]);
+        let m = ExhaustiveMap::from_hashmap_or_default(partial.clone());
+        for (k, v) in &m {
+            assert_eq!(*v, partial.get(&k).copied().unwrap_or_default());
+        }
+        assert_eq!(m[200], 2000);
+        assert_eq!(m[201], 0);
+
+        let full: HashMap<bool, u8> = HashMap::from([(false, 1), (true, 2)]);
+        assert_eq!(
+            ExhaustiveMap::from_hashmap_or_default(full.clone()),
+            ExhaustiveMap::try_from(full).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_zero_inhabitant_keys() {
+        type M = ExhaustiveMap<std::convert::Infallible, u8>;
+
+        let mut m = M::from_fn(|k| match k {});
+        assert_eq!(m, M::default());
+        assert_eq!(m, M::repeat(1));
+        assert_eq!(
+            M::try_from_fn(|k| -> Result<_, ()> { match k {} }),
+            Ok(M::default())
+        );
+        assert_eq!(M::from_usize_fn(|_| 1), M::default());
+        assert_eq!(M::from_iter_exact([]), Ok(M::default()));
+        assert_eq!(M::from_vec(vec![]), Ok(M::default()));
+        assert_eq!(M::LEN, 0);
+        assert!(m.is_empty());
+        assert!(m.as_slice().is_empty());
+        assert!(m.as_mut_slice().is_empty());
+        assert_eq!(m.at(0), Err(OutOfBounds { index: 0, len: 0 }));
+
+        let mut m2 = M::default();
+        m.swap_all(&mut m2);
+        assert_eq!(m.permute(&ExhaustiveMap::from_fn(|k| k)), Ok(()));
+        m.overwrite([]);
+        m.reset_if(|_, _| true, 0);
+        m.update_each(|v| *v += 1);
+        assert_eq!(M::keys().next(), None);
+        assert_eq!(M::fold_keys(0, |acc, _| acc + 1), 0);
+        assert_eq!(m.values().next(), None);
+        assert_eq!(m.values_mut().next(), None);
+        assert_eq!(m.entries_indexed().next(), None);
+        assert_eq!(m.enumerate_values_mut().next(), None);
+        assert_eq!(m.iter().next(), None);
+        assert_eq!(m.iter_mut().next(), None);
+        assert_eq!(m.pairs().next(), None);
+        assert_eq!(m.diff(&m2).next(), None);
+        assert_eq!(m.count(|_| true), 0);
+        assert_eq!(m.sum::<u8>(), 0);
+        assert_eq!(m.product::<u8>(), 1);
+        assert_eq!(m.reduce(0, |acc, _, _| acc + 1), 0);
+        assert_eq!(m.max_by_value(), None);
+        assert_eq!(m.min_by_value(), None);
+        assert_eq!(m.argmax(), None);
+        assert_eq!(m.argmin(), None);
+        assert_eq!(m.values_array(), Some([]));
+        assert_eq!(m.with_keys().iter().next(), None);
+        assert_eq!(m2.clone().map_values(|v| v + 1), ExhaustiveMap::default());
+        assert_eq!(m2.clone().into_values().next(), None);
+        assert_eq!(m2.clone().into_iter().next(), None);
+        assert_eq!(m2.clone().into_array(), Ok([]));
+        assert!(m2.into_vec().is_empty());
+        assert_eq!(format!("{:?}", M::default()), "{}");
+    }
+
+    #[test]
+    fn test_one_inhabitant_keys() {
+        type M = ExhaustiveMap<PhantomData<u8>, u8>;
+        let k = PhantomData;
+
+        let mut m = M::from_fn(|_| 5);
+        assert_eq!(m, M::repeat(5));
+        assert_eq!(M::from_iter_exact([5]), Ok(m.clone()));
+        assert_eq!(M::LEN, 1);
+        assert!(!m.is_empty());
+        assert_eq!(m.at(0), Ok(&5));
+        assert_eq!(m.at(1), Err(OutOfBounds { index: 1, len: 1 }));
+        assert_eq!(m.split_at_key(k), (&[][..], &[5][..]));
+
+        m.swap(k, k);
+        assert_eq!(m[k], 5);
+        assert_eq!(m.get_disjoint_mut(k, k), None);
+        assert_eq!(m.replace(k, 6), 5);
+        assert_eq!(m.take(k), 6);
+        assert_eq!(m.permute(&ExhaustiveMap::from_fn(|k| k)), Ok(()));
+        m.overwrite([(k, 7)]);
+        m.update_each(|v| *v += 1);
+        assert_eq!(m[k], 8);
+
+        assert_eq!(M::keys().collect::<Vec<_>>(), [k]);
+        assert_eq!(m.iter().collect::<Vec<_>>(), [(k, &8)]);
+        assert_eq!(m.iter_mut().next_back(), Some((k, &mut 8)));
+        assert_eq!(m.pairs().next(), None);
+        assert_eq!(m.diff(&M::default()).collect::<Vec<_>>(), [(k, &8, &0)]);
+        assert_eq!(m.count(|v| *v == 8), 1);
+        assert_eq!(m.sum::<u8>(), 8);
+        assert_eq!(m.product::<u8>(), 8);
+        assert_eq!(m.max_by_value(), Some((k, &8)));
+        assert_eq!(m.min_by_value(), Some((k, &8)));
+        assert_eq!(m.argmax(), Some(0));
+        assert_eq!(m.argmin(), Some(0));
+        assert_eq!(m.values_array(), Some([8]));
+        assert_eq!(m.clone().with_keys().keys(), [k]);
+        assert_eq!(m.clone().into_iter().collect::<Vec<_>>(), [(k, 8)]);
+        assert_eq!(m.into_array(), Ok([8]));
+    }
+
+    #[test]
+    fn test_pair_keys_with_uninhabited_component() {
+        use std::convert::Infallible;
+
+        type Left = ExhaustiveMap<(Infallible, bool), u8>;
+        type Right = ExhaustiveMap<(bool, Infallible), u8>;
+
+        let left = cartesian_product(|a: Infallible, _: bool| match a {});
+        let right = cartesian_product(|_: bool, b: Infallible| match b {});
+        assert_eq!(left, Left::default());
+        assert_eq!(right, Right::default());
+        assert!(left.is_empty());
+        assert!(right.is_empty());
+
+        let nested = ExhaustiveMap::<Infallible, ExhaustiveMap<bool, u8>>::default();
+        assert_eq!(nested.flatten(), left);
+        let nested = ExhaustiveMap::<bool, ExhaustiveMap<Infallible, u8>>::default();
+        assert_eq!(nested.flatten(), right);
+
+        let mut right = right;
+        assert_eq!(right.project(false).next(), None);
+        assert_eq!(right.project(true).next_back(), None);
+        assert_eq!(right.get_array_mut([]), Some([]));
+        assert_eq!(right.pairs().next(), None);
+        assert_eq!(right.iter().next(), None);
+        assert_eq!(right.diff(&Right::default()).next(), None);
+        assert_eq!(right.clone().with_keys().keys(), []);
+        assert_eq!(
+            right.clone().cast_keys::<Infallible>().map(|m| m.len()),
+            Ok(0)
+        );
+        assert_eq!(
+            right
+                .clone()
+                .rekey(|k: (Infallible, Infallible)| match k.0 {})
+                .map(|m| m.len()),
+            Ok(0)
+        );
+
+        let results = ExhaustiveMap::<(bool, Infallible), Result<u8, ()>>::repeat(Err(()));
+        assert_eq!(results.transpose(), Ok(Right::default()));
+        let options = ExhaustiveMap::<(Infallible, bool), Option<u8>>::default();
+        let merged = options.clone().merge_with(options.clone(), |a, b| a + b);
+        assert_eq!(merged, options);
+        assert_eq!(options.try_unwrap_values().ok(), Some(Left::default()));
+    }
+
+    #[test]
+    fn test_single() {
+        let mut m = ExhaustiveMap::single(vec![1, 2]);
+        assert_eq!(m.len(), 1);
+        m[()].push(3);
+        assert_eq!(m.iter().collect::<Vec<_>>(), [((), &vec![1, 2, 3])]);
+        assert_eq!(m.into_single(), [1, 2, 3]);
+
+        assert_eq!(ExhaustiveMap::from_fn(|()| 5).into_single(), 5);
+    }
+
+    #[test]
+    fn test_cartesian_product() {
+        let xor = cartesian_product(|a: bool, b: bool| a ^ b);
+        assert_eq!(
+            xor.iter().collect::<Vec<_>>(),
+            [
+                ((false, false), &false),
+                ((true, false), &true),
+                ((false, true), &true),
+                ((true, true), &false),
+            ]
+        );
+        assert_eq!(xor, ExhaustiveMap::from_fn(|(a, b)| a ^ b));
+    }
+
+    #[test]
+    fn test_project() {
+        let m = ExhaustiveMap::<(bool, u8), u16>::from_fn(|(b, k)| b as u16 * 1000 + k as u16);
+        let row: Vec<_> = m.project(true).collect();
+        assert_eq!(row.len(), 256);
+        for (k, &v) in row {
+            assert_eq!(v, 1000 + k as u16);
+        }
+        assert_eq!(m.project(false).next_back(), Some((255, &255)));
+
+        let nested = ExhaustiveMap::<u8, ExhaustiveMap<bool, u16>>::from_fn(|k| {
+            ExhaustiveMap::from_fn(|b| m[(b, k)])
+        });
+        let flat = nested.flatten();
+        for k in u8::iter_all() {
+            let row: Vec<_> = flat.project(k).map(|(_, v)| *v).collect();
+            assert_eq!(row, [k as u16, 1000 + k as u16]);
+        }
+
+        let empty = ExhaustiveMap::<(bool, std::convert::Infallible), u8>::from_fn(|_| 0);
+        assert_eq!(empty.project(true).next(), None);
+        assert_eq!(empty.project(false).len(), 0);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = ExhaustiveMap::<u8, Result<u8, u8>>::from_fn(Ok);
+        let expected = ExhaustiveMap::<u8, u8>::from_fn(|k| k);
+        assert_eq!(m.transpose(), Ok(expected));
+
+        let m =
+            ExhaustiveMap::<u8, Result<u8, u8>>::from_fn(
+                |k| if k % 7 == 3 { Err(k) } else { Ok(k) },
+            );
+        assert_eq!(m.transpose(), Err(3));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let nested = ExhaustiveMap::<bool, ExhaustiveMap<bool, u8>>::from_fn(|k1| {
+            ExhaustiveMap::from_fn(|k2| (k1 as u8) << 1 | k2 as u8)
+        });
+        let flat = nested.clone().flatten();
+        for (k1, k2) in <(bool, bool)>::iter_all() {
+            assert_eq!(flat[(k1, k2)], nested[k1][k2]);
+        }
+        assert_eq!(flat.into_values().collect::<Vec<_>>(), [0, 2, 1, 3]);
+    }
+
     #[test]
     fn test_try_unrwap_values() {
         let m: ExhaustiveMap<bool, Option<u8>> = ExhaustiveMap::from_fn(|_| None);
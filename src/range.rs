@@ -1,8 +1,68 @@
-use crate::Finite;
+use crate::{finite::char_to_usize, Finite};
 
 /// A `usize` value that is guaranteed to be in the range `A..B`.
 ///
 /// Common methods are in the [`InRangeBounds`] trait implementation.
+///
+/// The [`Finite`] implementation encodes a value `v` as `v - A`,
+/// so it can be used as a field of types deriving [`Finite`]:
+/// ```
+/// use exhaustive_map::{Finite, InRange, InRangeBounds};
+///
+/// #[derive(Finite, Debug, PartialEq)]
+/// struct Key {
+///     a: InRange<2, 5>,
+///     b: bool,
+/// }
+///
+/// assert_eq!(Key::INHABITANTS, 6);
+/// let key = Key {
+///     a: InRange::new(3).unwrap(),
+///     b: true,
+/// };
+/// assert_eq!(key.to_usize(), 4);
+/// assert_eq!(Key::from_usize(4), Some(key));
+/// ```
+///
+/// Addition saturates at the largest value in range, and converting to `f64` returns the value
+/// itself without any scaling. Together they can be used for a percentage type:
+/// ```
+/// use std::ops::Add;
+///
+/// use exhaustive_map::{Finite, FiniteExt, InRange, InRangeBounds};
+///
+/// #[derive(Finite, Debug, Clone, Copy, PartialEq)]
+/// struct Percent(InRange<0, 101>);
+///
+/// impl Percent {
+///     fn new(value: usize) -> Option<Self> {
+///         InRange::new(value).map(Self)
+///     }
+///
+///     fn as_fraction(self) -> f64 {
+///         f64::from(self.0) / 100.0
+///     }
+/// }
+///
+/// impl Add for Percent {
+///     type Output = Self;
+///
+///     fn add(self, rhs: Self) -> Self {
+///         Self(self.0 + rhs.0)
+///     }
+/// }
+///
+/// let full = Percent::new(100).unwrap();
+/// assert_eq!(full.as_fraction(), 1.0);
+/// assert_eq!(Percent::new(101), None);
+///
+/// let sixty = Percent::new(60).unwrap();
+/// assert_eq!((sixty + Percent::new(30).unwrap()).as_fraction(), 0.9);
+/// assert_eq!(sixty + sixty, full);
+///
+/// assert_eq!(Percent::INHABITANTS, 101);
+/// assert_eq!(Percent::iter_all().last(), Some(full));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct InRange<const A: usize, const B: usize>(usize);
 
@@ -48,6 +108,11 @@ pub trait InRangeBounds: Copy + Sized {
         Self::offset_from_start(i).is_some()
     }
 
+    /// An iterator over all values in range as `usize`s, in increasing order.
+    fn iter_values() -> impl DoubleEndedIterator<Item = usize> + ExactSizeIterator {
+        (0..Self::INHABITANTS).map(|offset| Self::MIN + offset)
+    }
+
     /// Creates a value if the given value is in range.
     fn new(i: usize) -> Option<Self> {
         if Self::in_bounds(i) {
@@ -57,6 +122,71 @@ pub trait InRangeBounds: Copy + Sized {
             None
         }
     }
+
+    /// Creates a value by clamping the given value to the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty, i.e. if `INHABITANTS` is zero,
+    /// as no values can be created in that case.
+    ///
+    /// ```
+    /// use exhaustive_map::{InRange, InRangeBounds};
+    ///
+    /// assert_eq!(InRange::<10, 20>::clamp_into(3).get(), 10);
+    /// assert_eq!(InRange::<10, 20>::clamp_into(15).get(), 15);
+    /// assert_eq!(InRange::<10, 20>::clamp_into(25).get(), 19);
+    /// ```
+    fn clamp_into(i: usize) -> Self {
+        assert!(Self::INHABITANTS != 0, "the range is empty");
+        let i = i.clamp(Self::MIN, Self::MIN + (Self::INHABITANTS - 1));
+        // SAFETY: `i` has been clamped to be in bounds.
+        unsafe { Self::new_unchecked(i) }
+    }
+}
+
+impl<const A: usize, const B: usize> InRange<A, B> {
+    /// Creates a value from a constant, checking at compile time that it is in range.
+    ///
+    /// ```
+    /// use exhaustive_map::{InRange, InRangeBounds};
+    ///
+    /// const V: InRange<1, 4> = InRange::new_const::<3>();
+    /// assert_eq!(V.get(), 3);
+    /// ```
+    ///
+    /// An out of range value fails to compile:
+    /// ```compile_fail
+    /// use exhaustive_map::InRange;
+    ///
+    /// let v = InRange::<1, 4>::new_const::<4>();
+    /// ```
+    pub const fn new_const<const V: usize>() -> Self {
+        const { assert!(A <= V && V < B, "the value must be in range") };
+        Self(V)
+    }
+}
+
+impl<const A: usize, const B: usize> InRangeInclusive<A, B> {
+    /// Creates a value from a constant, checking at compile time that it is in range.
+    ///
+    /// ```
+    /// use exhaustive_map::{InRangeBounds, InRangeInclusive};
+    ///
+    /// const V: InRangeInclusive<1, 4> = InRangeInclusive::new_const::<4>();
+    /// assert_eq!(V.get(), 4);
+    /// ```
+    ///
+    /// An out of range value fails to compile:
+    /// ```compile_fail
+    /// use exhaustive_map::InRangeInclusive;
+    ///
+    /// let v = InRangeInclusive::<1, 4>::new_const::<0>();
+    /// ```
+    pub const fn new_const<const V: usize>() -> Self {
+        const { assert!(A <= V && V <= B, "the value must be in range") };
+        Self(V)
+    }
 }
 
 impl<const A: usize, const B: usize> InRangeBounds for InRange<A, B> {
@@ -85,6 +215,31 @@ impl<const A: usize, const B: usize> InRangeBounds for InRangeInclusive<A, B> {
     }
 }
 
+macro_rules! impl_in_range_ops {
+    ($type:ident) => {
+        /// Adds the values, saturating at the largest value in range.
+        impl<const A: usize, const B: usize> std::ops::Add for $type<A, B> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self::clamp_into(self.get().saturating_add(rhs.get()))
+            }
+        }
+
+        /// Converts the value to an `f64`, rounding if it can't be represented exactly.
+        ///
+        /// The value isn't scaled, so e.g. a percentage must be divided by 100 to get a fraction.
+        impl<const A: usize, const B: usize> From<$type<A, B>> for f64 {
+            fn from(value: $type<A, B>) -> Self {
+                value.get() as f64
+            }
+        }
+    };
+}
+
+impl_in_range_ops!(InRange);
+impl_in_range_ops!(InRangeInclusive);
+
 impl<const A: usize, const B: usize> Finite for InRange<A, B> {
     const INHABITANTS: usize = <Self as InRangeBounds>::INHABITANTS;
 
@@ -109,6 +264,358 @@ impl<const A: usize, const B: usize> Finite for InRangeInclusive<A, B> {
     }
 }
 
+/// An `isize` value that is guaranteed to be in the range `A..B`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InRangeI<const A: isize, const B: isize>(isize);
+
+/// An `isize` value that is guaranteed to be in the range `A..=B`.
+///
+/// The full range `isize::MIN..=isize::MAX` has more than `usize::MAX` inhabitants,
+/// so `Finite` can't be used for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InRangeInclusiveI<const A: isize, const B: isize>(isize);
+
+macro_rules! impl_in_range_i {
+    ($type:ident, $inhabitants:expr) => {
+        impl<const A: isize, const B: isize> $type<A, B> {
+            /// The smallest value representable (if `INHABITANTS` is non-zero).
+            pub const MIN: isize = A;
+
+            /// Creates a value if the given value is in range.
+            pub fn new(i: isize) -> Option<Self> {
+                if i >= A && i.abs_diff(A) < <Self as Finite>::INHABITANTS {
+                    Some(Self(i))
+                } else {
+                    None
+                }
+            }
+
+            /// Returns the value as an `isize`.
+            pub fn get(self) -> isize {
+                self.0
+            }
+        }
+
+        impl<const A: isize, const B: isize> Finite for $type<A, B> {
+            const INHABITANTS: usize = {
+                assert!(A <= B, "the start of the range must not be after the end");
+                $inhabitants
+            };
+
+            fn to_usize(&self) -> usize {
+                self.0.abs_diff(A)
+            }
+
+            fn from_usize(i: usize) -> Option<Self> {
+                if i < Self::INHABITANTS {
+                    Some(Self(A.wrapping_add_unsigned(i)))
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+impl_in_range_i!(InRangeI, B.abs_diff(A));
+impl_in_range_i!(
+    InRangeInclusiveI,
+    crate::__inhabitants_add(B.abs_diff(A), 1)
+);
+
+/// A `char` value that is guaranteed to be in the range `A..=B`.
+///
+/// The [`Finite`] implementation only enumerates the chars in the range,
+/// in the same order as the [`Finite`] implementation for `char`.
+///
+/// ```
+/// use exhaustive_map::{CharRangeInclusive, Finite, FiniteExt};
+///
+/// type Lowercase = CharRangeInclusive<'a', 'z'>;
+///
+/// assert_eq!(Lowercase::INHABITANTS, 26);
+/// assert_eq!(Lowercase::new('c').unwrap().to_usize(), 2);
+/// assert_eq!(Lowercase::new('C'), None);
+/// assert_eq!(Lowercase::iter_all().map(Lowercase::get).last(), Some('z'));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CharRangeInclusive<const A: char, const B: char>(char);
+
+/// An ASCII `char`, i.e. a `char` in the range `'\0'..='\x7f'`.
+pub type AsciiChar = CharRangeInclusive<'\0', '\x7f'>;
+
+impl<const A: char, const B: char> CharRangeInclusive<A, B> {
+    /// Creates a value if the given value is in range.
+    pub fn new(c: char) -> Option<Self> {
+        if (A..=B).contains(&c) {
+            Some(Self(c))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value as a `char`.
+    pub fn get(self) -> char {
+        self.0
+    }
+}
+
+impl<const A: char, const B: char> Finite for CharRangeInclusive<A, B> {
+    const INHABITANTS: usize = {
+        assert!(A <= B, "the start of the range must not be after the end");
+        char_to_usize(B) - char_to_usize(A) + 1
+    };
+
+    fn to_usize(&self) -> usize {
+        char_to_usize(self.0) - char_to_usize(A)
+    }
+
+    fn from_usize(i: usize) -> Option<Self> {
+        if i < Self::INHABITANTS {
+            char::from_usize(char_to_usize(A) + i).map(Self)
+        } else {
+            None
+        }
+    }
+}
+
+/// A `u64` value that is a multiple of `STEP` in the range `A * STEP..B * STEP`,
+/// such as a duration in milliseconds quantized into buckets.
+///
+/// The [`Finite`] implementation has `B - A` inhabitants, one for each multiple of `STEP`.
+///
+/// ```
+/// use exhaustive_map::{ExhaustiveMap, Finite, Quantized};
+///
+/// // Durations from 0 ms to 1 s in 100 ms buckets
+/// type Bucket = Quantized<100, 0, 10>;
+///
+/// assert_eq!(Bucket::INHABITANTS, 10);
+/// assert_eq!(Bucket::new(300).unwrap().to_usize(), 3);
+/// assert_eq!(Bucket::new(350), None);
+/// assert_eq!(Bucket::quantize(350).unwrap().get(), 300);
+///
+/// let mut counts = ExhaustiveMap::<Bucket, u32>::default();
+/// for ms in [12, 99, 150, 999] {
+///     counts[Bucket::quantize(ms).unwrap()] += 1;
+/// }
+/// assert_eq!(counts[Bucket::new(0).unwrap()], 2);
+/// ```
+///
+/// A step of zero fails to compile:
+/// ```compile_fail
+/// use exhaustive_map::Quantized;
+///
+/// let v = Quantized::<0, 0, 10>::quantize(5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quantized<const STEP: u64, const A: u64, const B: u64>(u64);
+
+impl<const STEP: u64, const A: u64, const B: u64> Quantized<STEP, A, B> {
+    /// Creates a value if the given value is a multiple of `STEP` and is in range.
+    pub fn new(value: u64) -> Option<Self> {
+        const { assert!(STEP > 0, "the step must not be zero") };
+        if value.is_multiple_of(STEP) {
+            Self::quantize(value)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a value by rounding the given value down to a multiple of `STEP`,
+    /// if the result is in range.
+    pub fn quantize(value: u64) -> Option<Self> {
+        const { assert!(STEP > 0, "the step must not be zero") };
+        let step = value / STEP;
+        if (A..B).contains(&step) {
+            Some(Self(step * STEP))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value as a `u64`.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl<const STEP: u64, const A: u64, const B: u64> Finite for Quantized<STEP, A, B> {
+    const INHABITANTS: usize = {
+        assert!(STEP != 0, "the step must not be zero");
+        assert!(A <= B, "the start of the range must not be after the end");
+        assert!(
+            B.checked_mul(STEP).is_some(),
+            "the end of the range exceeds u64::MAX"
+        );
+        assert!(
+            B - A <= usize::MAX as u64,
+            "the number of inhabitants exceeds usize::MAX"
+        );
+        (B - A) as usize
+    };
+
+    fn to_usize(&self) -> usize {
+        (self.0 / STEP - A) as usize
+    }
+
+    fn from_usize(i: usize) -> Option<Self> {
+        if i < Self::INHABITANTS {
+            Some(Self((A + i as u64) * STEP))
+        } else {
+            None
+        }
+    }
+}
+
+/// A pair of values `start` and `end` of type `Idx`, where `start` is not after `end`
+/// in the order provided by [`Finite`].
+///
+/// Unlike `(Idx, Idx)` this excludes the invalid intervals, so it has `n * (n + 1) / 2`
+/// inhabitants where `n` is `Idx::INHABITANTS`.
+/// The intervals are ordered by `end`, and then by `start`.
+///
+/// ```
+/// use exhaustive_map::{Finite, InRange, Interval};
+///
+/// type Idx = InRange<0, 3>;
+/// let idx = |i| Idx::from_usize(i).unwrap();
+///
+/// assert_eq!(Interval::<Idx>::INHABITANTS, 6);
+/// assert_eq!(Interval::new(idx(0), idx(2)).unwrap().to_usize(), 3);
+/// assert_eq!(Interval::new(idx(2), idx(0)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<Idx> {
+    start: Idx,
+    end: Idx,
+}
+
+impl<Idx: Finite> Interval<Idx> {
+    /// Creates an interval if `start` is not after `end`.
+    pub fn new(start: Idx, end: Idx) -> Option<Self> {
+        if start.to_usize() <= end.to_usize() {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the start of the interval.
+    pub fn start(&self) -> &Idx {
+        &self.start
+    }
+
+    /// Returns the end of the interval.
+    pub fn end(&self) -> &Idx {
+        &self.end
+    }
+
+    /// Returns the start and end of the interval.
+    pub fn into_inner(self) -> (Idx, Idx) {
+        (self.start, self.end)
+    }
+}
+
+/// The number of intervals with an end at index less than `n`.
+const fn triangular(n: usize) -> usize {
+    if n.is_multiple_of(2) {
+        crate::__inhabitants_mul(n / 2, n + 1)
+    } else {
+        crate::__inhabitants_mul(n, n / 2 + 1)
+    }
+}
+
+impl<Idx: Finite> Finite for Interval<Idx> {
+    const INHABITANTS: usize = triangular(Idx::INHABITANTS);
+
+    fn to_usize(&self) -> usize {
+        triangular(self.end.to_usize()) + self.start.to_usize()
+    }
+
+    fn from_usize(i: usize) -> Option<Self> {
+        if i >= Self::INHABITANTS {
+            return None;
+        }
+        // The largest `end` with `triangular(end) <= i`.
+        let end = (((8 * i as u128 + 1).isqrt() - 1) / 2) as usize;
+        let start = i - triangular(end);
+        Some(Self {
+            start: Idx::from_usize(start)?,
+            end: Idx::from_usize(end)?,
+        })
+    }
+}
+
+/// Defines a newtype around [`InRange`] or [`InRangeInclusive`].
+///
+/// The newtype implements [`Finite`], [`Display`](std::fmt::Display) and the usual derivable
+/// traits, and has `new` and `get` methods like [`InRangeBounds`].
+///
+/// ```
+/// use exhaustive_map::{finite_range, ExhaustiveMap, Finite, FiniteExt};
+///
+/// finite_range!(
+///     /// An hour of the day.
+///     pub Hour, 0..24
+/// );
+/// finite_range!(Weekday, 1..=7);
+///
+/// let hour = Hour::new(23).unwrap();
+/// assert_eq!(hour.get(), 23);
+/// assert_eq!(hour.to_string(), "23");
+/// assert_eq!(Hour::new(24), None);
+/// assert_eq!(Hour::INHABITANTS, 24);
+/// assert_eq!(Weekday::iter_all().map(Weekday::get).last(), Some(7));
+///
+/// let hours = ExhaustiveMap::<Hour, bool>::from_fn(|h| (9..17).contains(&h.get()));
+/// assert!(hours[Hour::new(9).unwrap()]);
+/// ```
+#[macro_export]
+macro_rules! finite_range {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $a:literal .. $b:literal) => {
+        $crate::finite_range!(@impl $(#[$attr])* $vis $name, $crate::InRange<$a, $b>);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident, $a:literal ..= $b:literal) => {
+        $crate::finite_range!(@impl $(#[$attr])* $vis $name, $crate::InRangeInclusive<$a, $b>);
+    };
+    (@impl $(#[$attr:meta])* $vis:vis $name:ident, $inner:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($inner);
+
+        impl $name {
+            /// Creates a value if the given value is in range.
+            $vis fn new(i: usize) -> Option<Self> {
+                <$inner as $crate::InRangeBounds>::new(i).map(Self)
+            }
+
+            /// Returns the value as a `usize`.
+            $vis fn get(self) -> usize {
+                <$inner as $crate::InRangeBounds>::get(self.0)
+            }
+        }
+
+        impl $crate::Finite for $name {
+            const INHABITANTS: usize = <$inner as $crate::Finite>::INHABITANTS;
+
+            fn to_usize(&self) -> usize {
+                <$inner as $crate::Finite>::to_usize(&self.0)
+            }
+
+            fn from_usize(i: usize) -> Option<Self> {
+                <$inner as $crate::Finite>::from_usize(i).map(Self)
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.get(), f)
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod test {
     use std::{fmt::Debug, ops::RangeBounds};
@@ -136,6 +643,221 @@ mod test {
         test_range::<InRangeInclusive<1, { usize::MAX }>, _>(1..=usize::MAX);
     }
 
+    #[test]
+    fn test_clamp_into() {
+        type T = InRangeInclusive<5, 9>;
+        assert_eq!(T::clamp_into(0).get(), 5);
+        assert_eq!(T::clamp_into(5).get(), 5);
+        assert_eq!(T::clamp_into(7).get(), 7);
+        assert_eq!(T::clamp_into(9).get(), 9);
+        assert_eq!(T::clamp_into(usize::MAX).get(), 9);
+
+        type U = InRangeInclusive<1, { usize::MAX }>;
+        assert_eq!(U::clamp_into(0).get(), 1);
+        assert_eq!(U::clamp_into(usize::MAX).get(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic = "the range is empty"]
+    fn test_clamp_into_empty() {
+        InRange::<3, 3>::clamp_into(3);
+    }
+
+    #[test]
+    fn test_iter_values() {
+        assert_eq!(
+            InRange::<1, 4>::iter_values().collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            InRangeInclusive::<1, 3>::iter_values().collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(InRange::<5, 5>::iter_values().count(), 0);
+        assert_eq!(
+            InRangeInclusive::<1, { usize::MAX }>::iter_values().next_back(),
+            Some(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn test_new_const() {
+        assert_eq!(InRange::<0, 1>::new_const::<0>(), InRange::new(0).unwrap());
+        assert_eq!(
+            InRangeInclusive::<5, 9>::new_const::<9>(),
+            InRangeInclusive::new(9).unwrap()
+        );
+        assert_eq!(
+            InRangeInclusive::<{ usize::MAX }, { usize::MAX }>::new_const::<{ usize::MAX }>().get(),
+            usize::MAX
+        );
+    }
+
+    fn test_range_i<T: Finite + Debug + PartialEq>(
+        new: fn(isize) -> Option<T>,
+        get: fn(T) -> isize,
+        expected_range: impl RangeBounds<isize>,
+    ) {
+        for i in (-20..20)
+            .chain(isize::MIN..=isize::MIN + 5)
+            .chain(isize::MAX - 5..=isize::MAX)
+        {
+            let v = new(i);
+            if expected_range.contains(&i) {
+                let v = v.unwrap();
+                assert_eq!(T::from_usize(v.to_usize()).as_ref(), Some(&v));
+                assert_eq!(get(v), i);
+            } else {
+                assert_eq!(v, None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_range_i_symmetric() {
+        type T = InRangeInclusiveI<-10, 10>;
+        assert_eq!(T::INHABITANTS, 21);
+        assert_eq!(T::new(-10).unwrap().to_usize(), 0);
+        assert_eq!(T::new(0).unwrap().to_usize(), 10);
+        assert_eq!(T::from_usize(20).map(T::get), Some(10));
+        assert_eq!(T::from_usize(21), None);
+        test_range_i(T::new, T::get, -10..=10);
+        test_range_i(InRangeI::<-10, 10>::new, InRangeI::get, -10..10);
+    }
+
+    #[test]
+    fn test_in_range_i_negative() {
+        type T = InRangeI<-7, -3>;
+        assert_eq!(T::INHABITANTS, 4);
+        test_range_i(T::new, T::get, -7..-3);
+    }
+
+    #[test]
+    fn test_in_range_i_extremes() {
+        type T = InRangeI<{ isize::MIN }, { isize::MAX }>;
+        assert_eq!(T::INHABITANTS, usize::MAX);
+        assert_eq!(T::new(isize::MIN).unwrap().to_usize(), 0);
+        assert_eq!(T::new(isize::MAX - 1).unwrap().to_usize(), usize::MAX - 1);
+        test_range_i(T::new, T::get, isize::MIN..isize::MAX);
+
+        type U = InRangeInclusiveI<{ isize::MIN }, { isize::MIN + 2 }>;
+        assert_eq!(U::INHABITANTS, 3);
+        test_range_i(U::new, U::get, isize::MIN..=isize::MIN + 2);
+    }
+
+    #[test]
+    fn test_char_range_lowercase() {
+        type T = CharRangeInclusive<'a', 'z'>;
+        assert_eq!(T::INHABITANTS, 26);
+        for (i, c) in ('a'..='z').enumerate() {
+            let v = T::new(c).unwrap();
+            assert_eq!(v.get(), c);
+            assert_eq!(v.to_usize(), i);
+            assert_eq!(T::from_usize(i), Some(v));
+        }
+        assert_eq!(T::from_usize(26), None);
+        assert_eq!(T::new('`'), None);
+        assert_eq!(T::new('{'), None);
+    }
+
+    #[test]
+    fn test_char_range_across_gap() {
+        type T = CharRangeInclusive<'\u{D7FF}', '\u{E000}'>;
+        assert_eq!(T::INHABITANTS, 2);
+        assert_eq!(T::from_usize(1).map(T::get), Some('\u{E000}'));
+        assert_eq!(T::new('\u{E000}').unwrap().to_usize(), 1);
+    }
+
+    #[test]
+    fn test_ascii_char() {
+        assert_eq!(AsciiChar::INHABITANTS, 128);
+        assert_eq!(AsciiChar::new('A').unwrap().to_usize(), 65);
+        assert_eq!(AsciiChar::new('é'), None);
+    }
+
+    #[test]
+    fn test_percent() {
+        use crate::FiniteExt;
+
+        type Percent = InRange<0, 101>;
+        let full = Percent::new(100).unwrap();
+        assert_eq!(f64::from(full) / 100.0, 1.0);
+        assert_eq!(Percent::new(101), None);
+
+        let all: Vec<_> = Percent::iter_all().map(Percent::get).collect();
+        assert_eq!(all, (0..=100).collect::<Vec<_>>());
+
+        let sixty = Percent::new(60).unwrap();
+        assert_eq!((sixty + Percent::new(30).unwrap()).get(), 90);
+        assert_eq!(sixty + sixty, full);
+
+        type Small = InRangeInclusive<3, 7>;
+        let five = Small::new(5).unwrap();
+        assert_eq!((five + Small::new(3).unwrap()).get(), 7);
+        assert_eq!(f64::from(five), 5.0);
+    }
+
+    #[test]
+    fn test_quantized() {
+        use crate::FiniteExt;
+
+        type Bucket = Quantized<100, 0, 10>;
+        assert_eq!(Bucket::INHABITANTS, 10);
+        let all: Vec<_> = Bucket::iter_all().map(Bucket::get).collect();
+        assert_eq!(all, [0, 100, 200, 300, 400, 500, 600, 700, 800, 900]);
+        for (i, v) in Bucket::iter_all().enumerate() {
+            assert_eq!(v.to_usize(), i);
+        }
+        assert_eq!(Bucket::from_usize(10), None);
+        assert_eq!(Bucket::new(1000), None);
+        assert_eq!(Bucket::quantize(999).map(Bucket::get), Some(900));
+
+        type Offset = Quantized<3, 5, 7>;
+        assert_eq!(
+            Offset::iter_all().map(Offset::get).collect::<Vec<_>>(),
+            [15, 18]
+        );
+        assert_eq!(Offset::quantize(14), None);
+        assert_eq!(Offset::quantize(20).unwrap().to_usize(), 1);
+    }
+
+    #[test]
+    fn test_interval() {
+        use crate::FiniteExt;
+
+        type Idx = InRange<0, 3>;
+        assert_eq!(Interval::<Idx>::INHABITANTS, 6);
+        let all: Vec<_> = Interval::<Idx>::iter_all()
+            .map(|v| (v.start().get(), v.end().get()))
+            .collect();
+        assert_eq!(all, [(0, 0), (0, 1), (1, 1), (0, 2), (1, 2), (2, 2)]);
+        for (i, v) in Interval::<Idx>::iter_all().enumerate() {
+            assert_eq!(v.to_usize(), i);
+        }
+        assert_eq!(Interval::<Idx>::from_usize(6), None);
+
+        assert_eq!(Interval::<u8>::INHABITANTS, 256 * 257 / 2);
+        let v = Interval::new(100u8, 255).unwrap();
+        assert_eq!(Interval::from_usize(v.to_usize()), Some(v));
+        assert_eq!(Interval::<std::convert::Infallible>::INHABITANTS, 0);
+    }
+
+    #[test]
+    fn test_finite_range() {
+        use crate::FiniteExt;
+
+        finite_range!(Hour, 0..24);
+
+        let hour = Hour::new(23).unwrap();
+        assert_eq!(hour.get(), 23);
+        assert_eq!(hour.to_usize(), 23);
+        assert_eq!(hour.to_string(), "23");
+        assert_eq!(Hour::new(24), None);
+
+        let all: Vec<_> = Hour::iter_all().map(Hour::get).collect();
+        assert_eq!(all, (0..24).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_in_range() {
         test_range::<InRange<1, 3>, _>(1..3);
@@ -0,0 +1,156 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{Error, MapAccess, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{ExhaustiveMap, Finite, InRange, InRangeBounds, InRangeInclusive};
+
+/// Serialized as a map from keys to values.
+///
+/// Use [`serde_indexed`](crate::serde_indexed) to serialize without requiring `K: Serialize`.
+impl<K: Finite + Serialize, V: Serialize> Serialize for ExhaustiveMap<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self)
+    }
+}
+
+/// Deserialized from a map containing every key exactly once.
+impl<'de, K: Finite + Deserialize<'de>, V: Deserialize<'de>> Deserialize<'de>
+    for ExhaustiveMap<K, V>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+struct MapVisitor<K, V>(PhantomData<fn() -> (K, V)>);
+
+impl<'de, K: Finite + Deserialize<'de>, V: Deserialize<'de>> Visitor<'de> for MapVisitor<K, V> {
+    type Value = ExhaustiveMap<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a map with {} entries", K::INHABITANTS)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = ExhaustiveMap::<K, Option<V>>::default();
+        while let Some((k, v)) = access.next_entry::<K, V>()? {
+            if map[k].replace(v).is_some() {
+                return Err(A::Error::custom("duplicate key"));
+            }
+        }
+        map.try_unwrap_values()
+            .map_err(|_| A::Error::custom("missing key"))
+    }
+}
+
+// The impls for the range types are here rather than in `range.rs`, so that all the `serde`
+// code is in this module, next to the map impls using them for keys.
+macro_rules! impl_serde_in_range {
+    ($type:ident, $range:literal) => {
+        /// Serialized as the value as a `usize`.
+        impl<const A: usize, const B: usize> Serialize for $type<A, B> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.get().serialize(serializer)
+            }
+        }
+
+        /// Deserialized from a `usize`, failing if the value is not in range.
+        impl<'de, const A: usize, const B: usize> Deserialize<'de> for $type<A, B> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let i = usize::deserialize(deserializer)?;
+                Self::new(i).ok_or_else(|| {
+                    D::Error::invalid_value(
+                        Unexpected::Unsigned(i as u64),
+                        &format!(concat!("a value in the range {}", $range, "{}"), A, B).as_str(),
+                    )
+                })
+            }
+        }
+    };
+}
+
+impl_serde_in_range!(InRange, "..");
+impl_serde_in_range!(InRangeInclusive, "..=");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Finite, Serialize, Deserialize, Debug, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let m = ExhaustiveMap::<Color, u8>::from_usize_fn(|i| i as u8 * 10);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, r#"{"Red":0,"Green":10,"Blue":20}"#);
+        let m2: ExhaustiveMap<Color, u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(m, m2);
+
+        let m2: ExhaustiveMap<Color, u8> =
+            serde_json::from_str(r#"{"Blue":20,"Red":0,"Green":10}"#).unwrap();
+        assert_eq!(m, m2);
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let err =
+            serde_json::from_str::<ExhaustiveMap<Color, u8>>(r#"{"Red":0,"Blue":20}"#).unwrap_err();
+        assert!(err.to_string().starts_with("missing key"), "{err}");
+    }
+
+    #[test]
+    fn test_duplicate_key() {
+        let err = serde_json::from_str::<ExhaustiveMap<Color, u8>>(
+            r#"{"Red":0,"Green":10,"Red":0,"Blue":20}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("duplicate key"), "{err}");
+    }
+
+    #[test]
+    fn test_in_range_round_trip() {
+        let m = ExhaustiveMap::<InRange<2, 5>, u8>::from_usize_fn(|i| i as u8);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, r#"{"2":0,"3":1,"4":2}"#);
+        assert_eq!(
+            serde_json::from_str::<ExhaustiveMap<_, _>>(&json).ok(),
+            Some(m)
+        );
+
+        let v = InRangeInclusive::<2, 5>::new(5).unwrap();
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, "5");
+        assert_eq!(
+            serde_json::from_str::<InRangeInclusive<2, 5>>(&json).ok(),
+            Some(v)
+        );
+    }
+
+    #[test]
+    fn test_in_range_out_of_range() {
+        let err = serde_json::from_str::<InRange<2, 5>>("5").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid value: integer `5`, expected a value in the range 2..5"),
+            "{err}"
+        );
+        let err = serde_json::from_str::<InRangeInclusive<2, 5>>("1").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid value: integer `1`, expected a value in the range 2..=5"),
+            "{err}"
+        );
+        assert!(serde_json::from_str::<ExhaustiveMap<InRange<2, 5>, u8>>(
+            r#"{"2":0,"3":1,"4":2,"5":3}"#
+        )
+        .is_err());
+    }
+}
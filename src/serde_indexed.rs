@@ -0,0 +1,91 @@
+//! Serializes an [`ExhaustiveMap`] as a sequence of its values,
+//! ordered by the keys order provided by [`Finite`].
+//!
+//! Unlike the default [`Serialize`] and [`Deserialize`] implementations for [`ExhaustiveMap`],
+//! this doesn't require the key type to implement [`Serialize`] and [`Deserialize`].
+//! Note that the serialized data depends on the [`Finite`] implementation of the key.
+//!
+//! Requires the `serde` feature.
+//!
+//! ```
+//! use exhaustive_map::{ExhaustiveMap, Finite};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Finite)]
+//! enum Color {
+//!     Red,
+//!     Green,
+//!     Blue,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Palette {
+//!     #[serde(with = "exhaustive_map::serde_indexed")]
+//!     brightness: ExhaustiveMap<Color, u8>,
+//! }
+//!
+//! let palette = Palette {
+//!     brightness: ExhaustiveMap::from_usize_fn(|i| i as u8),
+//! };
+//! let json = serde_json::to_string(&palette).unwrap();
+//! assert_eq!(json, r#"{"brightness":[0,1,2]}"#);
+//! ```
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ExhaustiveMap, Finite};
+
+/// Serializes the values of the map as a sequence.
+pub fn serialize<K: Finite, V: Serialize, S: Serializer>(
+    map: &ExhaustiveMap<K, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(map.values())
+}
+
+/// Deserializes a map from a sequence of exactly `K::INHABITANTS` values.
+pub fn deserialize<'de, K: Finite, V: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ExhaustiveMap<K, V>, D::Error> {
+    let values = Vec::<V>::deserialize(deserializer)?;
+    let len = values.len();
+    ExhaustiveMap::try_from(values)
+        .map_err(|_| D::Error::invalid_length(len, &format!("{} values", K::INHABITANTS).as_str()))
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{ExhaustiveMap, Finite};
+
+    #[derive(Finite, Debug)]
+    struct NotSerialize(Option<bool>);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Wrapper {
+        #[serde(with = "crate::serde_indexed")]
+        map: ExhaustiveMap<NotSerialize, u8>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let w = Wrapper {
+            map: ExhaustiveMap::from_usize_fn(|i| i as u8 * 10),
+        };
+        let json = serde_json::to_string(&w).unwrap();
+        assert_eq!(json, r#"{"map":[0,10,20]}"#);
+        let w2: Wrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(w, w2);
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        let err = serde_json::from_str::<Wrapper>(r#"{"map":[0,10]}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid length 2, expected 3 values"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt::Debug;
+
+use crate::{ExhaustiveMap, Finite};
+
+/// The transition function of a finite state machine with states of type `S`
+/// and inputs of type `I`, storing the next state for every pair of state and input.
+///
+/// ```
+/// use exhaustive_map::{Finite, TransitionTable};
+///
+/// #[derive(Finite, Debug, Clone, Copy, PartialEq)]
+/// enum Parity {
+///     Even,
+///     Odd,
+/// }
+///
+/// let table = TransitionTable::from_fn(|state, bit: bool| match (state, bit) {
+///     (state, false) => state,
+///     (Parity::Even, true) => Parity::Odd,
+///     (Parity::Odd, true) => Parity::Even,
+/// });
+/// assert_eq!(table.step(Parity::Even, true), Parity::Odd);
+/// assert_eq!(
+///     table.run(Parity::Even, [true, false, true, true]),
+///     Parity::Odd
+/// );
+/// ```
+pub struct TransitionTable<S: Finite, I: Finite> {
+    table: ExhaustiveMap<(S, I), S>,
+}
+
+impl<S: Finite, I: Finite> TransitionTable<S, I> {
+    /// Creates a transition table from a map from `(state, input)` to the next state.
+    pub fn new(table: ExhaustiveMap<(S, I), S>) -> Self {
+        Self { table }
+    }
+
+    /// Creates a transition table by providing a function from the state and input to the next state.
+    pub fn from_fn(mut f: impl FnMut(S, I) -> S) -> Self {
+        Self::new(ExhaustiveMap::from_fn(|(state, input)| f(state, input)))
+    }
+
+    /// Returns the state following `state` on `input`.
+    pub fn step(&self, state: S, input: I) -> S
+    where
+        S: Clone,
+    {
+        self.table[(state, input)].clone()
+    }
+
+    /// Returns the state reached by starting in `start` and stepping on each of the `inputs` in order.
+    pub fn run(&self, start: S, inputs: impl IntoIterator<Item = I>) -> S
+    where
+        S: Clone,
+    {
+        inputs
+            .into_iter()
+            .fold(start, |state, input| self.step(state, input))
+    }
+
+    /// Returns the underlying map from `(state, input)` to the next state.
+    pub fn table(&self) -> &ExhaustiveMap<(S, I), S> {
+        &self.table
+    }
+
+    /// Converts the transition table into the underlying map from `(state, input)` to the next state.
+    pub fn into_table(self) -> ExhaustiveMap<(S, I), S> {
+        self.table
+    }
+}
+
+impl<S: Finite, I: Finite> From<ExhaustiveMap<(S, I), S>> for TransitionTable<S, I> {
+    fn from(table: ExhaustiveMap<(S, I), S>) -> Self {
+        Self::new(table)
+    }
+}
+
+impl<S: Finite + Debug, I: Finite + Debug> Debug for TransitionTable<S, I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransitionTable")
+            .field("table", &self.table)
+            .finish()
+    }
+}
+
+// The following traits could have been implemented using a derive macro,
+// however that would put an unnecessary trait bound on the input.
+
+impl<S: Finite + Clone, I: Finite> Clone for TransitionTable<S, I> {
+    fn clone(&self) -> Self {
+        Self::new(self.table.clone())
+    }
+}
+
+impl<S: Finite + PartialEq, I: Finite> PartialEq for TransitionTable<S, I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.table.eq(&other.table)
+    }
+}
+
+impl<S: Finite + Eq, I: Finite> Eq for TransitionTable<S, I> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Finite, Debug, Clone, Copy, PartialEq)]
+    enum Light {
+        Red,
+        Green,
+        Yellow,
+    }
+
+    #[derive(Finite, Debug, Clone, Copy, PartialEq)]
+    enum Event {
+        Timer,
+        Emergency,
+    }
+
+    #[test]
+    fn test_traffic_light() {
+        let table = TransitionTable::from_fn(|light, event| match (light, event) {
+            (_, Event::Emergency) => Light::Red,
+            (Light::Red, Event::Timer) => Light::Green,
+            (Light::Green, Event::Timer) => Light::Yellow,
+            (Light::Yellow, Event::Timer) => Light::Red,
+        });
+        assert_eq!(table.step(Light::Green, Event::Timer), Light::Yellow);
+        assert_eq!(table.step(Light::Green, Event::Emergency), Light::Red);
+
+        use Event::*;
+        assert_eq!(table.run(Light::Red, []), Light::Red);
+        assert_eq!(table.run(Light::Red, [Timer, Timer]), Light::Yellow);
+        assert_eq!(
+            table.run(Light::Red, [Timer, Emergency, Timer]),
+            Light::Green
+        );
+        assert_eq!(table.run(Light::Red, [Timer; 6]), Light::Red);
+
+        let table2 = TransitionTable::from(table.clone().into_table());
+        assert_eq!(table, table2);
+        assert_eq!(table.table().len(), 6);
+    }
+}
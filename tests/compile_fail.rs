@@ -0,0 +1,9 @@
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/*.rs");
+    #[cfg(target_pointer_width = "64")]
+    t.compile_fail("tests/fail/64-bit/*.rs");
+    #[cfg(target_pointer_width = "32")]
+    t.compile_fail("tests/fail/32-bit/*.rs");
+}
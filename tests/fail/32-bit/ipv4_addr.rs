@@ -0,0 +1,9 @@
+use std::net::Ipv4Addr;
+
+use exhaustive_map::Finite;
+
+fn assert_finite<T: Finite>() {}
+
+fn main() {
+    assert_finite::<Ipv4Addr>();
+}
@@ -0,0 +1,5 @@
+use exhaustive_map::Finite;
+
+const _: usize = <[u32; 3]>::INHABITANTS;
+
+fn main() {}
@@ -0,0 +1,5 @@
+use exhaustive_map::ExhaustiveArrayMap;
+
+const _: ExhaustiveArrayMap<bool, u8, 3> = ExhaustiveArrayMap::new([0; 3]);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use exhaustive_map::{exhaustive_map, ExhaustiveMap, Finite};
+
+#[derive(Finite, Debug, PartialEq)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+fn main() {
+    let _: ExhaustiveMap<Color, u8> = exhaustive_map! {
+        Color::Red => 1,
+        Color::Green => 2,
+    };
+}
@@ -0,0 +1,6 @@
+use exhaustive_map::{InRange, InRangeInclusive};
+
+const _: InRange<1, 4> = InRange::new_const::<4>();
+const _: InRangeInclusive<1, 4> = InRangeInclusive::new_const::<5>();
+
+fn main() {}
@@ -0,0 +1,10 @@
+use exhaustive_map::Finite;
+
+#[derive(Finite)]
+#[finite(max_inhabitants = 1024)]
+struct Key {
+    a: u8,
+    b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use exhaustive_map::Finite;
+
+#[derive(Finite)]
+#[non_exhaustive]
+enum Key {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use exhaustive_map::Finite;
+
+#[derive(Finite)]
+enum Tree {
+    Leaf,
+    Node(Box<Tree>, Box<Tree>),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use exhaustive_map::Finite;
+
+#[derive(Finite)]
+union Key {
+    a: u8,
+    b: bool,
+}
+
+fn main() {}